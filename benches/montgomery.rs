@@ -0,0 +1,110 @@
+//! Benchmark: aritmetica u64 con `u128 % p` frente al contexto de Montgomery,
+//! y suma de puntos individual frente a la suma por lotes (truco de Montgomery).
+//!
+//! Ejecutar con `cargo bench --bench montgomery`. Mide el coste por
+//! operacion sobre y^2 = x^3 + 7 con primos de 40, 50 y 60 bits.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use d3_symmetry::math::*;
+
+/// Primos p ≡ 1 (mod 3) de 40, 50 y 60 bits.
+const PRIMES: [u64; 3] = [1099511627689, 1125899906842597, 1152921504606846883];
+
+const B: u64 = 7;
+
+/// Nanosegundos por iteracion de `f`.
+fn ns_per_iter<T>(iters: u32, mut f: impl FnMut() -> T) -> f64 {
+    let start = Instant::now();
+    for _ in 0..iters {
+        black_box(f());
+    }
+    start.elapsed().as_nanos() as f64 / iters as f64
+}
+
+/// Primer punto de la curva con x >= 1.
+fn some_point(p: u64) -> Point {
+    (1..p)
+        .find_map(|x| {
+            let rhs = mod_add(mod_pow(x, 3, p), B, p);
+            mod_sqrt(rhs, p).map(|y| Point::new(x, y))
+        })
+        .expect("la curva tiene puntos")
+}
+
+fn report(name: &str, base: f64, mont: f64) {
+    println!("  {:<28} {:>10.1} ns {:>10.1} ns {:>7.2}x", name, base, mont, base / mont);
+}
+
+fn main() {
+    for &p in PRIMES.iter() {
+        let ctx = MontgomeryCtx::new(p);
+        let g = some_point(p);
+        assert!(is_on_curve(&g, B, p));
+
+        println!("p = {} ({} bits)", p, 64 - p.leading_zeros());
+        println!("  {:<28} {:>13} {:>13} {:>8}", "operacion", "u128 % p", "Montgomery", "speedup");
+
+        let x = g.x;
+        let xm = ctx.to_mont(x);
+        let mut acc = x;
+        let base = ns_per_iter(1_000_000, || {
+            acc = mod_mul(acc, x, p);
+            acc
+        });
+        let mut accm = xm;
+        let mont = ns_per_iter(1_000_000, || {
+            accm = ctx.mul(accm, xm);
+            accm
+        });
+        assert_eq!(ctx.from_mont(accm), acc);
+        report("mod_mul", base, mont);
+
+        // Camino R <- R + G, como los baby steps de BSGS
+        let mut r = g;
+        let base = ns_per_iter(50_000, || {
+            r = point_add(&r, &g, p);
+            r
+        });
+        let mut rm = g;
+        let mont = ns_per_iter(50_000, || {
+            rm = point_add_ctx(&rm, &g, &ctx);
+            rm
+        });
+        assert_eq!(r, rm);
+        report("point_add", base, mont);
+
+        let mut r = g;
+        let base = ns_per_iter(50_000, || {
+            r = point_double(&r, p);
+            r
+        });
+        let mut rm = g;
+        let mont = ns_per_iter(50_000, || {
+            rm = point_double_ctx(&rm, &ctx);
+            rm
+        });
+        assert_eq!(r, rm);
+        report("point_double", base, mont);
+
+        let k = p / 3 + 12345;
+        let base = ns_per_iter(500, || scalar_mul(k, &g, p));
+        let mont = ns_per_iter(500, || scalar_mul_ctx(k, &g, &ctx));
+        assert_eq!(scalar_mul(k, &g, p), scalar_mul_ctx(k, &g, &ctx));
+        report("scalar_mul", base, mont);
+
+        // Suma por lotes: 1024 pares (P_i, Q) con una sola inversion
+        let batch = 1024;
+        let p1s: Vec<Point> = (1..=batch).map(|i| scalar_mul_ctx(i, &g, &ctx)).collect();
+        let p2s = vec![scalar_mul_ctx(batch + 7, &g, &ctx); batch as usize];
+        let single = ns_per_iter(20, || {
+            p1s.iter().zip(&p2s).map(|(a, b)| point_add(a, b, p)).collect::<Vec<_>>()
+        }) / batch as f64;
+        let batched = ns_per_iter(20, || batch_point_add(&p1s, &p2s, p)) / batch as f64;
+        let expected: Vec<Point> = p1s.iter().zip(&p2s).map(|(a, b)| point_add(a, b, p)).collect();
+        assert_eq!(batch_point_add(&p1s, &p2s, p), expected);
+        println!("  {:<28} {:>10.1} ns {:>10.1} ns {:>7.2}x", "point_add por lote (1024)", single, batched, single / batched);
+        println!();
+    }
+}
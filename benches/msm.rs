@@ -0,0 +1,54 @@
+//! Benchmark: sum_i k_i * P_i sobre secp256k1 4x64.
+//!
+//! Ejecutar con `cargo bench --bench msm`. Compara la suma de
+//! multiplicaciones sueltas (wNAF) con Straus, Pippenger y el MSM con el
+//! endomorfismo plegado, para lotes de 2 a 1024 puntos. El cruce entre
+//! Straus y Pippenger fija `PIPPENGER_THRESHOLD`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use d3_symmetry::math::*;
+use d3_symmetry::math::secp256k1 as k1;
+use d3_symmetry::endomorphism::secp256k1 as k1_endo;
+
+const SIZES: [usize; 7] = [2, 8, 32, 128, 256, 512, 1024];
+
+/// Microsegundos por llamada a `f`, promediando `iters` llamadas.
+fn us_per_call<T>(iters: u32, mut f: impl FnMut() -> T) -> f64 {
+    let start = Instant::now();
+    for _ in 0..iters {
+        black_box(f());
+    }
+    start.elapsed().as_secs_f64() * 1e6 / iters as f64
+}
+
+fn main() {
+    let max = *SIZES.iter().max().expect("tamanos");
+    let mut s = k1::Scalar::from_u64(0x9E3779B97F4A7C15);
+    let mut scalars = Vec::with_capacity(max);
+    let mut points = Vec::with_capacity(max);
+    for i in 0..max as u64 {
+        s = s * s + k1::Scalar::from_u64(i);
+        scalars.push(s);
+        points.push(k1::mul_generator(&(s + k1::Scalar::ONE)));
+    }
+    let limbs: Vec<[u64; 4]> = scalars.iter().map(k1::Scalar::limbs).collect();
+
+    println!("{:>6} {:>12} {:>12} {:>12} {:>12}   (us por MSM)", "n", "sueltas", "Straus", "Pippenger", "GLV + msm");
+    for &n in SIZES.iter() {
+        let (ks, pts, ls) = (&scalars[..n], &points[..n], &limbs[..n]);
+        let iters = (2048 / n as u32).max(4);
+        let naive = || ks.iter().zip(pts).fold(None, |acc, (k, pt)| k1::point_add(&acc, &k1::scalar_mul(k, pt)));
+        let expected = naive();
+        assert_eq!(msm_straus(ls, pts), expected);
+        assert_eq!(msm_pippenger(ls, pts), expected);
+        assert_eq!(k1_endo::msm(ks, pts), expected);
+
+        let t_naive = us_per_call(iters, naive);
+        let t_straus = us_per_call(iters, || msm_straus(ls, pts));
+        let t_pipp = us_per_call(iters, || msm_pippenger(ls, pts));
+        let t_glv = us_per_call(iters, || k1_endo::msm(ks, pts));
+        println!("{:>6} {:>12.1} {:>12.1} {:>12.1} {:>12.1}", n, t_naive, t_straus, t_pipp, t_glv);
+    }
+}
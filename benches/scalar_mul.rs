@@ -0,0 +1,146 @@
+//! Benchmark: multiplicacion escalar binaria frente a wNAF y al comb de base fija.
+//!
+//! Ejecutar con `cargo bench --bench scalar_mul`. Compara, por backend:
+//! - toy u64 (Montgomery): double-and-add jacobiano, wNAF y comb de G;
+//! - BigUint sobre secp256k1: double-and-add afin y wNAF;
+//! - secp256k1 4x64: double-and-add afin, wNAF, GLV + Straus-Shamir y comb de G.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use num_bigint::BigUint;
+
+use d3_symmetry::math::*;
+use d3_symmetry::math::big;
+use d3_symmetry::math::secp256k1 as k1;
+use d3_symmetry::endomorphism::secp256k1 as k1_endo;
+
+/// Primos p ≡ 1 (mod 3) de 40 y 60 bits.
+const PRIMES: [u64; 2] = [1099511627689, 1152921504606846883];
+
+const B: u64 = 7;
+
+/// Nanosegundos por iteracion de `f`.
+fn ns_per_iter<T>(iters: u32, mut f: impl FnMut() -> T) -> f64 {
+    let start = Instant::now();
+    for _ in 0..iters {
+        black_box(f());
+    }
+    start.elapsed().as_nanos() as f64 / iters as f64
+}
+
+/// Primer punto de la curva con x >= 1.
+fn some_point(p: u64) -> Point {
+    (1..p)
+        .find_map(|x| {
+            let rhs = mod_add(mod_pow(x, 3, p), B, p);
+            mod_sqrt(rhs, p).map(|y| Point::new(x, y))
+        })
+        .expect("la curva tiene puntos")
+}
+
+fn report(name: &str, base: f64, new: f64) {
+    println!("  {:<28} {:>12.1} ns {:>12.1} ns {:>7.2}x", name, base, new, base / new);
+}
+
+fn header(title: &str) {
+    println!("{}", title);
+    println!("  {:<28} {:>15} {:>15} {:>8}", "operacion", "binario", "nuevo", "speedup");
+}
+
+/// Escalares pseudoaleatorios (xorshift) para no medir siempre el mismo k.
+fn scalars(count: usize) -> Vec<[u64; 4]> {
+    let mut x = 0x9E3779B97F4A7C15u64;
+    let mut next = move || {
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        x
+    };
+    (0..count).map(|_| [next(), next(), next(), next()]).collect()
+}
+
+fn main() {
+    let ks = scalars(64);
+
+    for &p in PRIMES.iter() {
+        let ctx = MontgomeryCtx::new(p);
+        let g = some_point(p);
+        let gm = g.to_mont(&ctx);
+        let bits = 64 - p.leading_zeros();
+        let comb = CombTable::new(gm.expect("G != O"), bits as usize, 4);
+        let mask = (1u64 << bits) - 1;
+
+        header(&format!("toy p = {} ({} bits, Montgomery)", p, bits));
+        let mut i = 0;
+        let base = ns_per_iter(20_000, || {
+            i = (i + 1) % ks.len();
+            jacobian_mul(&[ks[i][0] & mask], gm)
+        });
+        let new = ns_per_iter(20_000, || {
+            i = (i + 1) % ks.len();
+            wnaf_mul(&[ks[i][0] & mask], gm, wnaf_window(bits))
+        });
+        report("k*P (wNAF)", base, new);
+        let new = ns_per_iter(20_000, || {
+            i = (i + 1) % ks.len();
+            comb.mul(&[ks[i][0] & mask])
+        });
+        report("k*G (comb, 4 dientes)", base, new);
+        for k in &ks {
+            let k = k[0] & mask;
+            assert_eq!(jacobian_mul(&[k], gm), wnaf_mul(&[k], gm, wnaf_window(bits)));
+            assert_eq!(jacobian_mul(&[k], gm), comb.mul(&[k]));
+        }
+        println!();
+    }
+
+    let curve = big::secp256k1();
+    let big_ks: Vec<BigUint> = ks.iter().map(|k| k1::limbs_to_biguint(k) % &curve.n).collect();
+    header("BigUint secp256k1 (256 bits)");
+    let mut i = 0;
+    let base = ns_per_iter(50, || {
+        i = (i + 1) % big_ks.len();
+        big::scalar_mul_binary(&big_ks[i], &curve.g, &curve.p)
+    });
+    let new = ns_per_iter(50, || {
+        i = (i + 1) % big_ks.len();
+        big::scalar_mul(&big_ks[i], &curve.g, &curve.p)
+    });
+    assert_eq!(
+        big::scalar_mul_binary(&big_ks[0], &curve.g, &curve.p),
+        big::scalar_mul(&big_ks[0], &curve.g, &curve.p)
+    );
+    report("k*P (wNAF)", base, new);
+    println!();
+
+    let k1_ks: Vec<k1::Scalar> = big_ks.iter().map(k1::Scalar::from_biguint).collect();
+    // construir la tabla del comb fuera de la medida
+    black_box(k1::mul_generator(&k1_ks[0]));
+    header("secp256k1 4x64 (256 bits)");
+    let base = ns_per_iter(500, || {
+        i = (i + 1) % k1_ks.len();
+        affine_mul(&k1_ks[i].limbs(), k1::GENERATOR)
+    });
+    let new = ns_per_iter(500, || {
+        i = (i + 1) % k1_ks.len();
+        k1::scalar_mul(&k1_ks[i], &k1::GENERATOR)
+    });
+    report("k*P (wNAF)", base, new);
+    let new = ns_per_iter(500, || {
+        i = (i + 1) % k1_ks.len();
+        k1_endo::glv_mul(&k1_ks[i], &k1::GENERATOR)
+    });
+    report("k*P (GLV + Straus-Shamir)", base, new);
+    let new = ns_per_iter(500, || {
+        i = (i + 1) % k1_ks.len();
+        k1::mul_generator(&k1_ks[i])
+    });
+    report("k*G (comb, 8 dientes)", base, new);
+    for k in &k1_ks {
+        let expected = affine_mul(&k.limbs(), k1::GENERATOR);
+        assert_eq!(k1::scalar_mul(k, &k1::GENERATOR), expected);
+        assert_eq!(k1_endo::glv_mul(k, &k1::GENERATOR), expected);
+        assert_eq!(k1::mul_generator(k), expected);
+    }
+}
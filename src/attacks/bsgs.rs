@@ -1,14 +1,86 @@
 /// Ataques ECDLP: BSGS estandar vs BSGS con automorfismos (D=-3).
+///
+/// Ambos resuelven Q = k*G en un `Subgroup` y solo aceptan un Q validado
+/// (`validate_point`) contra ese mismo subgrupo, asi que k siempre existe.
+/// El BSGS estandar funciona sobre cualquier curva y^2 = x^3 + ax + b; la
+/// variante GLV requiere a = 0.
 
 use std::collections::HashMap;
+use crate::error::{Error, Result};
 use crate::math::field::*;
+use crate::math::fp::*;
 use crate::math::curve::*;
+use crate::math::jacobian::*;
+use crate::math::weierstrass::Curve;
 use crate::endomorphism::*;
 
-/// BSGS estandar: resuelve Q = k*G en el grupo de orden n.
+/// Giant steps normalizados a afin en lotes de este tamano: una
+/// inversion por lote en lugar de una por paso.
+const GIANT_BATCH: u64 = 64;
+
+/// Iterador de giant steps Q, Q + S, Q + 2S, ... (S = -m*G). Avanza en
+/// jacobianas con sumas mixtas y solo normaliza cuando hace falta la clave
+/// de la tabla, lote a lote con `batch_normalize`.
+struct GiantSteps {
+    gamma: Jacobian<MontFp>,
+    step: Option<(MontFp, MontFp)>,
+    a: MontFp,
+    remaining: u64,
+    buf: std::vec::IntoIter<Point>,
+}
+
+impl GiantSteps {
+    fn new(q: &Point, step: &Point, a: u64, count: u64, ctx: &MontgomeryCtx) -> Self {
+        let like = MontFp::new(0, ctx);
+        GiantSteps {
+            gamma: Jacobian::from_affine(q.to_mont(ctx), &like),
+            step: step.to_mont(ctx),
+            a: MontFp::new(a, ctx),
+            remaining: count,
+            buf: Vec::new().into_iter(),
+        }
+    }
+}
+
+impl Iterator for GiantSteps {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Point> {
+        if let Some(pt) = self.buf.next() {
+            return Some(pt);
+        }
+        if self.remaining == 0 {
+            return None;
+        }
+        let len = GIANT_BATCH.min(self.remaining);
+        let mut chunk = Vec::with_capacity(len as usize);
+        for _ in 0..len {
+            chunk.push(self.gamma);
+            self.gamma = self.gamma.add_affine_a(self.step, self.a);
+        }
+        self.remaining -= len;
+        let pts: Vec<Point> = batch_normalize(&chunk).into_iter().map(Point::from_mont).collect();
+        self.buf = pts.into_iter();
+        self.buf.next()
+    }
+}
+
+/// BSGS estandar: resuelve Q = k*G en <G>, sin explotar automorfismos.
 /// Retorna (k, numero_de_operaciones).
-pub fn bsgs_standard(q: &Point, g: &Point, n: u64, p: u64) -> (u64, usize) {
+pub fn bsgs_standard(sub: &Subgroup, q: &ValidatedPoint) -> (u64, usize) {
+    try_bsgs_standard(sub, q).unwrap_or_else(|e| panic!("BSGS estandar: {}", e))
+}
+
+/// Como `bsgs_standard`, con error `NotInSubgroup` si Q se valido contra
+/// otro subgrupo.
+pub fn try_bsgs_standard(sub: &Subgroup, q: &ValidatedPoint) -> Result<(u64, usize)> {
+    bsgs_with_a(sub.member(q)?, &sub.g, sub.n, sub.curve.a, sub.curve.p)
+}
+
+/// Nucleo del BSGS estandar; `a` solo interviene al doblar.
+fn bsgs_with_a(q: &Point, g: &Point, n: u64, a: u64, p: u64) -> Result<(u64, usize)> {
     let m = (n as f64).sqrt().ceil() as u64;
+    let ctx = MontgomeryCtx::new(p);
     let mut ops = 0usize;
 
     // Baby steps: tabla[j*G] = j para j = 0..m-1
@@ -21,17 +93,17 @@ pub fn bsgs_standard(q: &Point, g: &Point, n: u64, p: u64) -> (u64, usize) {
             // Representar el punto infinito con un valor especial
             table.insert((u64::MAX, u64::MAX), 0);
         }
-        baby = point_add(&baby, g, p);
+        baby = point_add_ctx_a(&baby, g, a, &ctx);
         ops += 1;
     }
 
     // Giant step: factor = m*G
-    let factor = scalar_mul(m, g, p);
+    let factor = scalar_mul_ctx_a(m, g, a, &ctx);
     let neg_factor = point_neg(&factor, p);
 
     // Giant steps: Q - i*m*G para i = 0..m
-    let mut gamma = *q;
-    for i in 0..=m {
+    for (i, gamma) in GiantSteps::new(q, &neg_factor, a, m + 1, &ctx).enumerate() {
+        let i = i as u64;
         let key = if gamma.infinity {
             (u64::MAX, u64::MAX)
         } else {
@@ -39,23 +111,82 @@ pub fn bsgs_standard(q: &Point, g: &Point, n: u64, p: u64) -> (u64, usize) {
         };
 
         if let Some(&j) = table.get(&key) {
-            let k = mod_add(mod_mul(i, m, n), j, n);
-            return (k, ops);
+            let k = Scalar::new(i, n) * m + Scalar::new(j, n);
+            return Ok((k.value(), ops));
+        }
+        ops += 1;
+    }
+
+    Err(Error::NoSolution)
+}
+
+/// BSGS acotado a un intervalo sobre una curva general: resuelve Q = k*G
+/// sabiendo que lo <= k < lo + width, con O(sqrt(width)) operaciones y sin
+/// conocer el orden de G (version u64 de `bsgs_interval`).
+/// Retorna (k, numero_de_operaciones); error `PointNotOnCurve` si Q o G no
+/// estan en la curva, `IntervalOverflow` si lo + width - 1 no cabe en u64 y
+/// `NoSolution` si k no esta en el intervalo.
+pub fn try_bsgs_interval_on_curve(
+    ec: &Curve, q: &Point, g: &Point, lo: u64, width: u64,
+) -> Result<(u64, usize)> {
+    if !ec.is_on_curve(q) || !ec.is_on_curve(g) {
+        return Err(Error::PointNotOnCurve);
+    }
+    if lo.checked_add(width.saturating_sub(1)).is_none() {
+        return Err(Error::IntervalOverflow { lo, width });
+    }
+    let (a, p) = (ec.a, ec.p);
+    let m = (width as f64).sqrt().ceil().max(1.0) as u64;
+    let ctx = MontgomeryCtx::new(p);
+    let key = |pt: &Point| if pt.infinity { (u64::MAX, u64::MAX) } else { (pt.x, pt.y) };
+    let mut ops = 0usize;
+
+    // Baby steps: tabla[j*G] = j para j = 0..m-1 (el primer j si G tiene orden < m)
+    let mut table: HashMap<(u64, u64), u64> = HashMap::new();
+    let mut baby = Point::infinity();
+    for j in 0..m {
+        table.entry(key(&baby)).or_insert(j);
+        baby = point_add_ctx_a(&baby, g, a, &ctx);
+        ops += 1;
+    }
+
+    // Desplazar al origen del intervalo: Q' = Q - lo*G = (k - lo)*G
+    let shift = point_neg(&scalar_mul_ctx_a(lo, g, a, &ctx), p);
+    let start = point_add_ctx_a(q, &shift, a, &ctx);
+    let neg_factor = point_neg(&scalar_mul_ctx_a(m, g, a, &ctx), p);
+
+    // Giant steps: Q' - i*m*G para i = 0..ceil(width/m)
+    for (i, gamma) in GiantSteps::new(&start, &neg_factor, a, width.div_ceil(m), &ctx).enumerate() {
+        if let Some(&j) = table.get(&key(&gamma)) {
+            // en u128: con width cerca de 2^64, i*m + j puede pasar de u64
+            let offset = i as u128 * m as u128 + j as u128;
+            if offset < width as u128 {
+                return Ok((lo + offset as u64, ops));
+            }
         }
-        gamma = point_add(&gamma, &neg_factor, p);
         ops += 1;
     }
 
-    panic!("BSGS estandar no encontro solucion");
+    Err(Error::NoSolution)
 }
 
 /// BSGS con automorfismos D=-3: usa clases de equivalencia de tamanio 6.
 /// Almacena representantes canonicos en la tabla baby, reduciendo el espacio sqrt(6) veces.
 /// Retorna (k, numero_de_operaciones).
-pub fn bsgs_glv(q: &Point, g: &Point, n: u64, p: u64, beta: u64) -> (u64, usize) {
+pub fn bsgs_glv(sub: &Subgroup, q: &ValidatedPoint) -> (u64, usize) {
+    try_bsgs_glv(sub, q).unwrap_or_else(|e| panic!("BSGS GLV: {}", e))
+}
+
+/// Como `bsgs_glv`, con el error de `Subgroup::endomorphism` si la curva no
+/// admite phi y `NotInSubgroup` si Q se valido contra otro subgrupo.
+pub fn try_bsgs_glv(sub: &Subgroup, q: &ValidatedPoint) -> Result<(u64, usize)> {
+    let q = sub.member(q)?;
+    let (beta, lambda) = sub.endomorphism()?;
+    let (g, n, p) = (&sub.g, sub.n, sub.curve.p);
     // Con |Aut(E)| = 6, el tamano optimo de la tabla es sqrt(n/6)
     let m = ((n as f64) / 6.0).sqrt().ceil() as u64;
     let m = if m == 0 { 1 } else { m };
+    let ctx = MontgomeryCtx::new(p);
     let mut ops = 0usize;
 
     // Baby steps: almacenamos canonical_rep(j*G) -> j
@@ -68,27 +199,26 @@ pub fn bsgs_glv(q: &Point, g: &Point, n: u64, p: u64, beta: u64) -> (u64, usize)
         } else if j == 0 {
             table.insert((u64::MAX, u64::MAX), 0);
         }
-        baby = point_add(&baby, g, p);
+        baby = point_add_ctx(&baby, g, &ctx);
         ops += 1;
     }
 
     // Giant step: factor = m*G
-    let factor = scalar_mul(m, g, p);
+    let factor = scalar_mul_ctx(m, g, &ctx);
     let neg_factor = point_neg(&factor, p);
 
     // Para recuperar k, necesitamos saber QUE automorfismo matcheo
-    let lambda = find_lambda(n);
-    let lambda2 = mod_mul(lambda, lambda, n);
+    let scalars = orbit_scalars(Scalar::new(lambda, n));
 
     // Giant steps: Q - i*m*G para i = 0..ceil(n/m)+1
     let max_giant = n / m + 2;
-    let mut gamma = *q;
-    for i in 0..max_giant {
+    for (i, gamma) in GiantSteps::new(q, &neg_factor, 0, max_giant, &ctx).enumerate() {
+        let i = i as u64;
         if gamma.infinity {
             if table.contains_key(&(u64::MAX, u64::MAX)) {
                 let j = table[&(u64::MAX, u64::MAX)];
-                let k = mod_add(mod_mul(i, m, n), j, n);
-                return (k, ops);
+                let k = Scalar::new(i, n) * m + Scalar::new(j, n);
+                return Ok((k.value(), ops));
             }
         } else {
             let canon = canonical_rep(&gamma, beta, p);
@@ -96,9 +226,7 @@ pub fn bsgs_glv(q: &Point, g: &Point, n: u64, p: u64, beta: u64) -> (u64, usize)
                 // gamma esta en la orbita de j*G
                 // gamma = sigma(j*G) para algun automorfismo sigma
                 // Necesitamos encontrar cual sigma y ajustar k
-                let jg = scalar_mul(j, g, p);
-                let orbit_scalars = [1u64, lambda, lambda2,
-                                     n - 1, n - lambda, n - lambda2];
+                let jg = scalar_mul_ctx(j, g, &ctx);
                 let orbit_points = automorphism_orbit(&jg, beta, p);
 
                 for (idx, orbit_pt) in orbit_points.iter().enumerate() {
@@ -106,17 +234,14 @@ pub fn bsgs_glv(q: &Point, g: &Point, n: u64, p: u64, beta: u64) -> (u64, usize)
                         // gamma = [orbit_scalars[idx]] * (j*G)
                         // Q - i*m*G = [s] * j*G
                         // Q = i*m*G + s*j*G
-                        let s = orbit_scalars[idx];
-                        let sj = mod_mul(s, j, n);
-                        let k = mod_add(mod_mul(i, m, n), sj, n);
-                        return (k, ops);
+                        let k = Scalar::new(i, n) * m + scalars[idx] * j;
+                        return Ok((k.value(), ops));
                     }
                 }
             }
         }
-        gamma = point_add(&gamma, &neg_factor, p);
         ops += 1;
     }
 
-    panic!("BSGS GLV no encontro solucion");
+    Err(Error::NoSolution)
 }
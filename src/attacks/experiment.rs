@@ -0,0 +1,102 @@
+/// Experimentos repetidos: coste medio de los cuatro ataques sobre
+/// instancias aleatorias reproducibles.
+///
+/// Una sola clave no basta para medir el factor sqrt(6): el numero de giant
+/// steps depende de k y las iteraciones de Rho del camino. `run_trials`
+/// toma en cada ensayo un generador aleatorio del subgrupo de orden primo
+/// mas grande, una clave k uniforme en [1, n) y semillas de Rho nuevas,
+/// todo derivado de `seed`, y promedia operaciones e iteraciones.
+
+use crate::error::{Error, Result};
+use crate::math::rng::Rng;
+use crate::math::weierstrass::Curve;
+use crate::endomorphism::subgroup::*;
+use super::bsgs::*;
+use super::pollard_rho::*;
+
+/// Media y desviacion tipica de una serie de ensayos.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Sample {
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+impl Sample {
+    fn from_values(values: &[f64]) -> Self {
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let var = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0).max(1.0);
+        Sample { mean, stddev: var.sqrt() }
+    }
+}
+
+/// Coste de los cuatro ataques promediado sobre `trials` instancias.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrialStats {
+    pub trials: usize,
+    /// Orden primo n de los subgrupos atacados.
+    pub n: u64,
+    pub bsgs_standard: Sample,
+    pub bsgs_glv: Sample,
+    pub rho_standard: Sample,
+    pub rho_glv: Sample,
+}
+
+impl TrialStats {
+    /// Cociente de medias BSGS estandar / BSGS GLV.
+    pub fn bsgs_speedup(&self) -> f64 {
+        self.bsgs_standard.mean / self.bsgs_glv.mean
+    }
+
+    /// Cociente de medias Rho estandar / Rho GLV.
+    pub fn rho_speedup(&self) -> f64 {
+        self.rho_standard.mean / self.rho_glv.mean
+    }
+}
+
+/// Instancia aleatoria en <G>: k uniforme en [1, n) y Q = [k]G validado.
+pub fn random_instance(sub: &Subgroup, rng: &mut Rng) -> (u64, ValidatedPoint) {
+    let k = rng.scalar(sub.n);
+    let q = sub.curve.scalar_mul(k, &sub.g);
+    (k, validate_point(sub, &q).expect("[k]G esta en <G>"))
+}
+
+/// Subgrupo de orden primo mas grande con generador aleatorio.
+pub fn random_subgroup(curve: &Curve, order: u64, rng: &mut Rng) -> Result<Subgroup> {
+    let (g, n, h) = curve.random_prime_order_generator(order, rng)?;
+    Subgroup::try_new(*curve, g, n, h)
+}
+
+/// Ejecuta `trials` ensayos sobre y^2 = x^3 + b (j = 0, #E = `order`).
+/// Error si la curva no admite GLV o si algun ataque devuelve una clave
+/// incorrecta (`NoSolution`).
+pub fn run_trials(curve: &Curve, order: u64, trials: usize, seed: u64) -> Result<TrialStats> {
+    assert!(trials > 0, "se necesita al menos un ensayo");
+    let mut rng = Rng::new(seed);
+    let mut costs: [Vec<f64>; 4] = Default::default();
+    let mut n = 0;
+    for _ in 0..trials {
+        let mut trial = rng.fork();
+        let sub = random_subgroup(curve, order, &mut trial)?;
+        let (k, q) = random_instance(&sub, &mut trial);
+        let check = |found: u64| -> Result<()> {
+            let matches = found == k || sub.curve.scalar_mul(found, &sub.g) == *q.point();
+            if matches { Ok(()) } else { Err(Error::NoSolution) }
+        };
+
+        let (k_std, ops_std) = try_bsgs_standard(&sub, &q)?;
+        let (k_glv, ops_glv) = try_bsgs_glv(&sub, &q)?;
+        let rho_std = try_pollard_rho_standard(&sub, &q, trial.next_u64())?;
+        let rho_glv = try_pollard_rho_glv(&sub, &q, trial.next_u64())?;
+        for found in [k_std, k_glv, rho_std.k, rho_glv.k] {
+            check(found)?;
+        }
+        for (acc, cost) in costs.iter_mut().zip([ops_std, ops_glv, rho_std.iterations, rho_glv.iterations]) {
+            acc.push(cost as f64);
+        }
+        n = sub.n;
+    }
+    let [bsgs_standard, bsgs_glv, rho_standard, rho_glv] = costs.map(|v| Sample::from_values(&v));
+    Ok(TrialStats { trials, n, bsgs_standard, bsgs_glv, rho_standard, rho_glv })
+}
+
@@ -0,0 +1,46 @@
+/// BSGS acotado a un intervalo sobre el backend BigUint.
+///
+/// Con parametros reales (secp256k1) el ECDLP completo es inviable, pero si
+/// se sabe que k esta en [lo, lo + width) basta con O(sqrt(width)) operaciones.
+/// Es el escenario de claves con entropia reducida o de known-answer tests.
+
+use std::collections::HashMap;
+use num_bigint::BigUint;
+
+use crate::math::big::*;
+
+/// Resuelve Q = k*G sabiendo que lo <= k < lo + width.
+/// Retorna Some((k, numero_de_operaciones)), o None si k no esta en el intervalo.
+pub fn bsgs_interval(
+    q: &BigPoint, g: &BigPoint, lo: &BigUint, width: u64, p: &BigUint,
+) -> Option<(BigUint, usize)> {
+    let m = (width as f64).sqrt().ceil().max(1.0) as u64;
+    let mut ops = 0usize;
+
+    // Baby steps: tabla[j*G] = j para j = 0..m-1
+    let mut table: HashMap<BigPoint, u64> = HashMap::new();
+    let mut baby = BigPoint::infinity();
+    for j in 0..m {
+        table.entry(baby.clone()).or_insert(j);
+        baby = point_add(&baby, g, p);
+        ops += 1;
+    }
+
+    // Desplazar al origen del intervalo: Q' = Q - lo*G = (k - lo)*G
+    let shift = point_neg(&scalar_mul(lo, g, p), p);
+    let mut gamma = point_add(q, &shift, p);
+
+    // Giant steps: Q' - i*m*G para i = 0..ceil(width/m)
+    let neg_factor = point_neg(&scalar_mul(&BigUint::from(m), g, p), p);
+    let max_giant = width.div_ceil(m);
+    for i in 0..max_giant {
+        if let Some(&j) = table.get(&gamma) {
+            let k = lo + BigUint::from(i) * m + j;
+            return Some((k, ops));
+        }
+        gamma = point_add(&gamma, &neg_factor, p);
+        ops += 1;
+    }
+
+    None
+}
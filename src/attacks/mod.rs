@@ -2,10 +2,19 @@
 ///
 /// - `bsgs`: Baby-step Giant-step (estándar y con GLV/D=-3)
 /// - `pollard_rho`: Pollard's Rho con Equivalence Class Search (D=-3)
+/// - `interval`: BSGS acotado a un intervalo sobre parámetros reales (BigUint)
+/// - `order`: orden de puntos y conteo de Mestre con BSGS en el intervalo de Hasse
+/// - `experiment`: coste medio de los ataques sobre instancias aleatorias con semilla
 
 pub mod bsgs;
 pub mod pollard_rho;
+pub mod interval;
+pub mod order;
+pub mod experiment;
 
 // Re-exportar para acceso directo: d3_symmetry::attacks::*
 pub use bsgs::*;
 pub use pollard_rho::*;
+pub use interval::*;
+pub use order::*;
+pub use experiment::*;
@@ -0,0 +1,122 @@
+/// Orden de puntos y conteo de puntos por BSGS en el intervalo de Hasse (Mestre).
+///
+/// #E(F_p) esta en [p + 1 - 2 sqrt(p), p + 1 + 2 sqrt(p)], unos 4 sqrt(p)
+/// enteros. Para un punto P, `try_bsgs_interval_on_curve` encuentra un
+/// multiplo de ord(P) en ese intervalo con O(p^(1/4)) operaciones, y la
+/// factorizacion del multiplo lo reduce a ord(P).
+///
+/// Si un solo multiplo de ord(P) cae en el intervalo, ese es #E. Si no,
+/// `mestre_count` acumula el mcm de los ordenes de puntos aleatorios de E y
+/// de su twist cuadratico E' (#E + #E' = 2p + 2) hasta que un unico
+/// candidato es compatible con ambos; por el teorema de Mestre esto termina
+/// para p > 229. Entre la fuerza bruta y Schoof: con p de 48 bits las
+/// tablas tienen ~2^13 puntos y el conteo tarda milisegundos.
+
+use num_integer::Integer;
+
+use crate::error::{Error, Result};
+use crate::math::curve::*;
+use crate::math::group::order_from_multiple;
+use crate::math::rng::Rng;
+use crate::math::weierstrass::Curve;
+use super::bsgs::try_bsgs_interval_on_curve;
+
+/// Por debajo de este primo el teorema de Mestre no garantiza un candidato
+/// unico y se cuenta por fuerza bruta.
+const MESTRE_MIN_P: u64 = 229;
+
+/// Candidatos que se recorren como maximo al combinar los mcm de E y E'.
+const MAX_SCAN: u64 = 1 << 16;
+
+/// Intervalo de Hasse [p + 1 - floor(2 sqrt(p)), p + 1 + floor(2 sqrt(p))]
+/// (ver `try_hasse_interval`).
+pub fn hasse_interval(p: u64) -> (u64, u64) {
+    try_hasse_interval(p).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Como `hasse_interval`, con error `HasseOverflow` si el extremo superior
+/// no cabe en u64 (p a menos de ~2^33 de 2^64).
+pub fn try_hasse_interval(p: u64) -> Result<(u64, u64)> {
+    let r = (4 * p as u128).isqrt() as u64;
+    let hi = p.checked_add(1 + r).ok_or(Error::HasseOverflow { p })?;
+    Ok((p + 1 - r, hi))
+}
+
+/// Orden de un punto de cualquier curva (ver `try_point_order`).
+pub fn point_order(ec: &Curve, pt: &Point) -> u64 {
+    try_point_order(ec, pt).unwrap_or_else(|e| panic!("orden del punto: {}", e))
+}
+
+/// Orden de P buscando con BSGS un multiplo en el intervalo de Hasse, sin
+/// conocer #E. Error `PointNotOnCurve` si P no esta en la curva y
+/// `HasseOverflow` si el intervalo no cabe en u64.
+pub fn try_point_order(ec: &Curve, pt: &Point) -> Result<u64> {
+    if pt.infinity {
+        return Ok(1);
+    }
+    let (lo, hi) = try_hasse_interval(ec.p)?;
+    let (m, _) = try_bsgs_interval_on_curve(ec, &Point::infinity(), pt, lo, hi - lo + 1)?;
+    Ok(order_from_multiple(ec, pt, m))
+}
+
+/// Multiplos de `step` en [lo, hi].
+fn multiples_in(step: u128, lo: u128, hi: u128) -> u128 {
+    hi / step - (lo - 1) / step
+}
+
+/// Hasta dos candidatos N en [lo, hi] con L_E | N y L_T | 2p + 2 - N,
+/// recorriendo los multiplos del mcm con menos candidatos.
+fn candidates(l_e: u64, l_t: u64, p: u64) -> Vec<u64> {
+    let (lo, hi) = hasse_interval(p);
+    // 2p + 2 pasa de u64 con p >= 2^63; los candidatos si caben
+    let (lo, hi, sum) = (lo as u128, hi as u128, 2 * p as u128 + 2);
+    let (l_e, l_t) = (l_e as u128, l_t as u128);
+    let mut found = Vec::new();
+    if multiples_in(l_e, lo, hi) <= multiples_in(l_t, lo, hi) {
+        let mut n = lo.div_ceil(l_e) * l_e;
+        while n <= hi && found.len() < 2 {
+            if (sum - n).is_multiple_of(l_t) {
+                found.push(n as u64);
+            }
+            n += l_e;
+        }
+    } else {
+        let mut n = lo.div_ceil(l_t) * l_t;
+        while n <= hi && found.len() < 2 {
+            if (sum - n).is_multiple_of(l_e) {
+                found.push((sum - n) as u64);
+            }
+            n += l_t;
+        }
+    }
+    found
+}
+
+/// #E(F_p) por el metodo de Mestre con puntos derivados de `seed`.
+/// Para p <= 229 usa `count_points`. Panics si el intervalo de Hasse no
+/// cabe en u64 (ver `try_hasse_interval`).
+pub fn mestre_count(ec: &Curve, seed: u64) -> u64 {
+    assert!(ec.p > 3 && !ec.is_singular(), "Mestre requiere p > 3 y una curva no singular");
+    if ec.p <= MESTRE_MIN_P {
+        return ec.count_points();
+    }
+    let twist = ec.quadratic_twist();
+    let (lo, hi) = hasse_interval(ec.p);
+    let mut rng = Rng::new(seed);
+    let (mut l_e, mut l_t) = (1u64, 1u64);
+    loop {
+        for (curve, lcm) in [(ec, &mut l_e), (&twist, &mut l_t)] {
+            let pt = curve.random_point(&mut rng);
+            let order = try_point_order(curve, &pt).expect("punto de la curva");
+            *lcm = lcm.lcm(&order);
+        }
+        // con mcm pequenos hay demasiados candidatos: seguir muestreando
+        let scan = |l: u64| multiples_in(l as u128, lo as u128, hi as u128);
+        if scan(l_e).min(scan(l_t)) > MAX_SCAN as u128 {
+            continue;
+        }
+        if let [n] = candidates(l_e, l_t, ec.p)[..] {
+            return n;
+        }
+    }
+}
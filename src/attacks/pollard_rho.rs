@@ -6,52 +6,85 @@
 /// La variante GLV explota los 6 automorfismos de curvas con |D|=3:
 /// en cada paso, el punto se reduce a su representante canónico,
 /// comprimiendo el espacio de búsqueda por un factor √6.
+///
+/// Ambas variantes trabajan en un `Subgroup` y exigen un Q validado contra
+/// ese mismo subgrupo: con Q fuera de <G> el camino nunca colisiona con una
+/// relacion util y el bucle no termina. La estándar acepta cualquier curva y^2 = x^3 + ax + b; la
+/// variante GLV requiere a = 0.
+///
+/// El camino es un r-adding walk (Teske): R' = R + M_i con i = x mod r y
+/// M_i = [c_i]G + [d_i]Q. Los multiplicadores y el punto de partida
+/// R_0 = [a_0]G + [b_0]Q salen de `seed`: la misma semilla repite la
+/// ejecucion y semillas distintas dan caminos independientes para promediar
+/// iteraciones.
+///
+/// Con clases canonicas aparecen ciclos infructuosos: si canon(R + M_i)
+/// vuelve a caer en la particion i y es -(R + M_i), el paso siguiente
+/// regresa a R. La variante GLV mira un paso por delante y usa M_(i+1) en
+/// ese caso, lo que elimina los ciclos de longitud 2; una colision
+/// degenerada (b_t = b_h, sin informacion sobre k) reinicia con otra tabla
+/// y otro R_0, porque en subgrupos pequenos todos los ciclos de un mismo
+/// camino pueden ser infructuosos.
 
+use crate::error::Result;
+use crate::math::rng::Rng;
 use crate::math::field::*;
+use crate::math::fp::*;
 use crate::math::curve::*;
 use crate::endomorphism::*;
 
+/// Particiones del r-adding walk (Teske recomienda r = 20).
+const WALK_PARTITIONS: usize = 20;
+
 /// Resultado de Pollard's Rho: clave encontrada + estadísticas.
 pub struct PollardRhoResult {
     pub k: u64,
     pub iterations: usize,
 }
 
+/// Multiplicador M_i = c_i G + d_i Q de una particion.
+type Multiplier = (Point, Scalar, Scalar);
+
+/// R = a G + b Q con a, b uniformes mod n, descartando O.
+fn random_combination(sub: &Subgroup, q: &Point, rng: &mut Rng) -> Multiplier {
+    loop {
+        let (a, b) = (rng.below(sub.n), rng.below(sub.n));
+        let r = sub.curve.point_add(&sub.curve.scalar_mul(a, &sub.g), &sub.curve.scalar_mul(b, q));
+        if !r.infinity {
+            return (r, Scalar::new(a, sub.n), Scalar::new(b, sub.n));
+        }
+    }
+}
+
+/// Tabla de multiplicadores del camino, uno por particion.
+fn walk_table(sub: &Subgroup, q: &Point, rng: &mut Rng) -> Vec<Multiplier> {
+    (0..WALK_PARTITIONS).map(|_| random_combination(sub, q, rng)).collect()
+}
+
 // ── Función de partición ──────────────────────────────────────
-// Divide los puntos en 3 zonas según x mod 3 para definir
-// el camino pseudoaleatorio. Cada zona aplica una operación
-// distinta para mezclar los coeficientes (a, b) de R = aG + bQ.
+// Divide los puntos en r zonas según x mod r; la zona i suma M_i
+// y actualiza los coeficientes (a, b) de R = aG + bQ.
+
+fn partition(r: &Point) -> usize {
+    if r.infinity { 0 } else { (r.x % WALK_PARTITIONS as u64) as usize }
+}
+
+/// Paso por la zona i: R' = R + M_i, a' = a + c_i, b' = b + d_i.
+/// `curve_a` es el coeficiente a de la curva, necesario si R = M_i.
+fn add_step(
+    r: &Point, a: Scalar, b: Scalar,
+    m: &Multiplier, curve_a: u64, ctx: &MontgomeryCtx,
+) -> (Point, Scalar, Scalar) {
+    let (mp, c, d) = m;
+    (point_add_ctx_a(r, mp, curve_a, ctx), a + *c, b + *d)
+}
 
 /// Un paso del camino aleatorio: dado R = aG + bQ, produce R', a', b'.
-/// La partición usa x mod 3 del punto (o hash del infinito).
 fn walk_step(
-    r: &Point, a: u64, b: u64,
-    g: &Point, q: &Point,
-    n: u64, p: u64,
-) -> (Point, u64, u64) {
-    let partition = if r.infinity { 0 } else { r.x % 3 };
-
-    match partition {
-        // Zona 0: R' = R + Q,  a' = a,      b' = b + 1
-        0 => {
-            let r_new = point_add(r, q, p);
-            let b_new = mod_add(b, 1, n);
-            (r_new, a, b_new)
-        }
-        // Zona 1: R' = 2R,     a' = 2a,     b' = 2b
-        1 => {
-            let r_new = point_add(r, r, p);
-            let a_new = mod_mul(2, a, n);
-            let b_new = mod_mul(2, b, n);
-            (r_new, a_new, b_new)
-        }
-        // Zona 2: R' = R + G,  a' = a + 1,  b' = b
-        _ => {
-            let r_new = point_add(r, g, p);
-            let a_new = mod_add(a, 1, n);
-            (r_new, a_new, b)
-        }
-    }
+    r: &Point, a: Scalar, b: Scalar,
+    table: &[Multiplier], curve_a: u64, ctx: &MontgomeryCtx,
+) -> (Point, Scalar, Scalar) {
+    add_step(r, a, b, &table[partition(r)], curve_a, ctx)
 }
 
 // ── Pollard's Rho estándar ────────────────────────────────────
@@ -59,29 +92,37 @@ fn walk_step(
 /// Pollard's Rho estándar: resuelve Q = k*G sin explotar automorfismos.
 /// Memoria O(1), tiempo esperado O(√(π·n/2)).
 /// Retorna PollardRhoResult con k y el número de iteraciones.
-pub fn pollard_rho_standard(
-    q: &Point, g: &Point, n: u64, p: u64,
-) -> PollardRhoResult {
-    // Tortuga: empieza en R_t = G (a=1, b=0)
-    let mut rt = *g;
-    let mut at: u64 = 1;
-    let mut bt: u64 = 0;
+pub fn pollard_rho_standard(sub: &Subgroup, q: &ValidatedPoint, seed: u64) -> PollardRhoResult {
+    try_pollard_rho_standard(sub, q, seed).unwrap_or_else(|e| panic!("Pollard rho estandar: {}", e))
+}
+
+/// Como `pollard_rho_standard`, con error `NotInSubgroup` si Q se valido
+/// contra otro subgrupo.
+pub fn try_pollard_rho_standard(sub: &Subgroup, q: &ValidatedPoint, seed: u64) -> Result<PollardRhoResult> {
+    let q = sub.member(q)?;
+    let mut rng = Rng::new(seed);
+    let curve_a = sub.curve.a;
+    let ctx = MontgomeryCtx::new(sub.curve.p);
+    let mut table = walk_table(sub, q, &mut rng);
+
+    // Tortuga: empieza en R_0 = a_0 G + b_0 Q
+    let (mut rt, mut at, mut bt) = random_combination(sub, q, &mut rng);
 
     // Liebre: empieza en el mismo punto
-    let mut rh = *g;
-    let mut ah: u64 = 1;
-    let mut bh: u64 = 0;
+    let mut rh = rt;
+    let mut ah = at;
+    let mut bh = bt;
 
     let mut iterations = 0usize;
 
     loop {
         // Tortuga: 1 paso
-        let (rt2, at2, bt2) = walk_step(&rt, at, bt, g, q, n, p);
+        let (rt2, at2, bt2) = walk_step(&rt, at, bt, &table, curve_a, &ctx);
         rt = rt2; at = at2; bt = bt2;
 
         // Liebre: 2 pasos
-        let (rh2, ah2, bh2) = walk_step(&rh, ah, bh, g, q, n, p);
-        let (rh3, ah3, bh3) = walk_step(&rh2, ah2, bh2, g, q, n, p);
+        let (rh2, ah2, bh2) = walk_step(&rh, ah, bh, &table, curve_a, &ctx);
+        let (rh3, ah3, bh3) = walk_step(&rh2, ah2, bh2, &table, curve_a, &ctx);
         rh = rh3; ah = ah3; bh = bh3;
 
         iterations += 1;
@@ -92,115 +133,115 @@ pub fn pollard_rho_standard(
             // (at - ah)*G = (bh - bt)*Q
             // Si Q = k*G => (at - ah) = k*(bh - bt) mod n
             // k = (at - ah) * (bh - bt)^{-1} mod n
-            let delta_b = mod_sub(bh, bt, n);
-            if delta_b == 0 {
-                // Colisión degenerada: reintentar con offset
-                // (en curva toy esto es rarísimo, pero lo manejamos)
-                rt = point_add(&rt, g, p);
-                at = mod_add(at, 1, n);
+            let delta_a = at - ah;
+            let Some(inv_delta_b) = (bh - bt).inv() else {
+                // Colisión degenerada: reiniciar con otro camino y otro R_0
+                table = walk_table(sub, q, &mut rng);
+                (rt, at, bt) = random_combination(sub, q, &mut rng);
                 rh = rt; ah = at; bh = bt;
                 continue;
-            }
-            let delta_a = mod_sub(at, ah, n);
-            let k = mod_mul(delta_a, mod_inv(delta_b, n), n);
-            return PollardRhoResult { k, iterations };
+            };
+            let k = (delta_a * inv_delta_b).value();
+            return Ok(PollardRhoResult { k, iterations });
         }
     }
 }
 
 // ── Pollard's Rho con Equivalence Class Search (D=-3) ─────────
 
-/// Un paso del camino aleatorio con reducción canónica.
-/// Después de cada paso, el punto se reemplaza por su representante
-/// canónico (el menor de los 6 automorfismos), y los coeficientes
-/// (a, b) se ajustan según qué automorfismo lo mapeó.
-fn walk_step_canonical(
-    r: &Point, a: u64, b: u64,
-    g: &Point, q: &Point,
-    n: u64, p: u64, beta: u64,
-    lambda: u64, lambda2: u64,
-) -> (Point, u64, u64) {
-    // Primero: paso normal del camino aleatorio
-    let (r_new, a_new, b_new) = walk_step(r, a, b, g, q, n, p);
-
-    if r_new.infinity {
-        return (r_new, a_new, b_new);
+/// Reemplaza R = aG + bQ por su representante canónico (el menor de los
+/// 6 automorfismos) y ajusta (a, b) según qué automorfismo lo mapeó.
+fn to_canonical(
+    r: Point, a: Scalar, b: Scalar,
+    beta: u64, p: u64, scalars: &[Scalar; 6],
+) -> (Point, Scalar, Scalar) {
+    if r.infinity {
+        return (r, a, b);
     }
-
-    // Después: reducir a representante canónico
-    // Encontrar cuál de los 6 automorfismos produce el representante
-    let canon = canonical_rep(&r_new, beta, p);
+    let canon = canonical_rep(&r, beta, p);
 
     // Los 6 automorfismos con sus escalares asociados:
     // σ_i(P) = [s_i]*P donde s_i ∈ {1, λ, λ², -1, -λ, -λ²}
-    let orbit_scalars = [1u64, lambda, lambda2,
-                         n - 1, n - lambda, n - lambda2];
-    let orbit = automorphism_orbit(&r_new, beta, p);
+    let orbit = automorphism_orbit(&r, beta, p);
 
     for (idx, orbit_pt) in orbit.iter().enumerate() {
         if *orbit_pt == canon {
-            // canon = σ_i(R') = [s_i] * R'
-            // R' = a'G + b'Q, canon = [s_i]*(a'G + b'Q)
-            // canon = (s_i*a')G + (s_i*b')Q
-            let s = orbit_scalars[idx];
-            let a_canon = mod_mul(s, a_new, n);
-            let b_canon = mod_mul(s, b_new, n);
-            return (canon, a_canon, b_canon);
+            // canon = σ_i(R) = [s_i] * R
+            // R = aG + bQ, canon = [s_i]*(aG + bQ)
+            // canon = (s_i*a)G + (s_i*b)Q
+            let s = scalars[idx];
+            return (canon, s * a, s * b);
         }
     }
 
     // Fallback: no debería llegar aquí
-    (r_new, a_new, b_new)
+    (r, a, b)
+}
+
+/// Un paso del camino aleatorio con reducción canónica y un paso de
+/// anticipación: si canon(R + M_i) cae de nuevo en la zona i se usa
+/// M_(i+1), para no entrar en un ciclo infructuoso de longitud 2.
+fn walk_step_canonical(
+    r: &Point, a: Scalar, b: Scalar,
+    table: &[Multiplier], ctx: &MontgomeryCtx,
+    beta: u64, scalars: &[Scalar; 6],
+) -> (Point, Scalar, Scalar) {
+    let i = partition(r);
+    let (r_new, a_new, b_new) = add_step(r, a, b, &table[i], 0, ctx);
+    let next = to_canonical(r_new, a_new, b_new, beta, ctx.p, scalars);
+    if partition(&next.0) != i {
+        return next;
+    }
+    let (r_new, a_new, b_new) = add_step(r, a, b, &table[(i + 1) % table.len()], 0, ctx);
+    to_canonical(r_new, a_new, b_new, beta, ctx.p, scalars)
 }
 
 /// Pollard's Rho con Equivalence Class Search: explota |Aut(E)| = 6.
 /// Cada paso reduce el punto a su clase canónica, comprimiendo el
 /// espacio de colisión por √6 ≈ 2.45x respecto al Rho estándar.
 /// Memoria O(1), tiempo esperado O(√(π·n/12)).
-pub fn pollard_rho_glv(
-    q: &Point, g: &Point, n: u64, p: u64, beta: u64,
-) -> PollardRhoResult {
-    let lambda = find_lambda(n);
-    let lambda2 = mod_mul(lambda, lambda, n);
-
-    // Tortuga: empieza en canon(G)
-    let start = canonical_rep(g, beta, p);
-    // Necesitamos saber qué escalar mapea G → canon(G)
-    let orbit_scalars = [1u64, lambda, lambda2,
-                         n - 1, n - lambda, n - lambda2];
-    let orbit = automorphism_orbit(g, beta, p);
-    let mut a_start: u64 = 1;
-    let b_start: u64 = 0;
-    for (idx, orbit_pt) in orbit.iter().enumerate() {
-        if *orbit_pt == start {
-            a_start = orbit_scalars[idx];
-            break;
-        }
-    }
+pub fn pollard_rho_glv(sub: &Subgroup, q: &ValidatedPoint, seed: u64) -> PollardRhoResult {
+    try_pollard_rho_glv(sub, q, seed).unwrap_or_else(|e| panic!("Pollard rho GLV: {}", e))
+}
 
-    let mut rt = start;
-    let mut at = a_start;
-    let mut bt = b_start;
+/// Como `pollard_rho_glv`, con el error de `Subgroup::endomorphism` si la
+/// curva no admite phi y `NotInSubgroup` si Q se valido contra otro subgrupo.
+pub fn try_pollard_rho_glv(sub: &Subgroup, q: &ValidatedPoint, seed: u64) -> Result<PollardRhoResult> {
+    let q = sub.member(q)?;
+    let (beta, lambda) = sub.endomorphism()?;
+    let mut rng = Rng::new(seed);
+    let (n, p) = (sub.n, sub.curve.p);
+    let ctx = MontgomeryCtx::new(p);
+    let scalars = orbit_scalars(Scalar::new(lambda, n));
+    let start = |rng: &mut Rng| {
+        let (r, a, b) = random_combination(sub, q, rng);
+        to_canonical(r, a, b, beta, p, &scalars)
+    };
+    let mut table = walk_table(sub, q, &mut rng);
 
-    let mut rh = start;
-    let mut ah = a_start;
-    let mut bh = b_start;
+    // Tortuga: empieza en canon(R_0)
+    let (mut rt, mut at, mut bt) = start(&mut rng);
+
+    // Liebre: empieza en el mismo punto
+    let mut rh = rt;
+    let mut ah = at;
+    let mut bh = bt;
 
     let mut iterations = 0usize;
 
     loop {
         // Tortuga: 1 paso canónico
         let (rt2, at2, bt2) = walk_step_canonical(
-            &rt, at, bt, g, q, n, p, beta, lambda, lambda2,
+            &rt, at, bt, &table, &ctx, beta, &scalars,
         );
         rt = rt2; at = at2; bt = bt2;
 
         // Liebre: 2 pasos canónicos
         let (rh2, ah2, bh2) = walk_step_canonical(
-            &rh, ah, bh, g, q, n, p, beta, lambda, lambda2,
+            &rh, ah, bh, &table, &ctx, beta, &scalars,
         );
         let (rh3, ah3, bh3) = walk_step_canonical(
-            &rh2, ah2, bh2, g, q, n, p, beta, lambda, lambda2,
+            &rh2, ah2, bh2, &table, &ctx, beta, &scalars,
         );
         rh = rh3; ah = ah3; bh = bh3;
 
@@ -208,19 +249,16 @@ pub fn pollard_rho_glv(
 
         // ¿Colisión en espacio canónico?
         if rt == rh {
-            let delta_b = mod_sub(bh, bt, n);
-            if delta_b == 0 {
-                // Colisión degenerada: perturbar y reintentar
-                let (rt2, at2, bt2) = walk_step_canonical(
-                    &rt, at, bt, g, q, n, p, beta, lambda, lambda2,
-                );
-                rt = rt2; at = at2; bt = bt2;
+            let delta_a = at - ah;
+            let Some(inv_delta_b) = (bh - bt).inv() else {
+                // Colisión degenerada o ciclo infructuoso: otro camino y otro R_0
+                table = walk_table(sub, q, &mut rng);
+                (rt, at, bt) = start(&mut rng);
                 rh = rt; ah = at; bh = bt;
                 continue;
-            }
-            let delta_a = mod_sub(at, ah, n);
-            let k = mod_mul(delta_a, mod_inv(delta_b, n), n);
-            return PollardRhoResult { k, iterations };
+            };
+            let k = (delta_a * inv_delta_b).value();
+            return Ok(PollardRhoResult { k, iterations });
         }
     }
 }
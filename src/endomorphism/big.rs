@@ -0,0 +1,62 @@
+/// Endomorfismo GLV sobre el backend BigUint (parametros reales, p.ej. secp256k1).
+
+use num_bigint::BigUint;
+use num_traits::One;
+
+use crate::math::big::*;
+
+/// Encuentra beta: raiz cubica primitiva de 1 mod p.
+/// Requiere p ≡ 1 (mod 3).
+pub fn find_beta(p: &BigUint) -> BigUint {
+    assert!(
+        (p % 3u32).is_one(),
+        "p debe ser ≡ 1 (mod 3) para que exista beta"
+    );
+
+    // Buscamos g tal que g^((p-1)/3) != 1, luego beta = g^((p-1)/3)
+    let exp = (p - 1u32) / 3u32;
+    let mut g = BigUint::from(2u32);
+    while &g < p {
+        let candidate = mod_pow(&g, &exp, p);
+        if !candidate.is_one() {
+            return candidate;
+        }
+        g += 1u32;
+    }
+    panic!("No se encontro beta");
+}
+
+/// Encuentra lambda: raiz de x^2 + x + 1 ≡ 0 (mod n), con n primo.
+///
+/// A diferencia de la version u64 no se busca por fuerza bruta:
+/// x = (-1 + sqrt(-3)) / 2 (mod n). La otra raiz es n - 1 - lambda = lambda^2.
+pub fn find_lambda(n: &BigUint) -> BigUint {
+    let minus_3 = n - 3u32;
+    let s = mod_sqrt(&minus_3, n)
+        .expect("No se encontro lambda (n no es compatible con D=-3)");
+    let inv2 = mod_inv(&BigUint::from(2u32), n);
+    mod_mul(&mod_sub(&s, &BigUint::one(), n), &inv2, n)
+}
+
+/// Aplica el endomorfismo phi(P) = (beta * x, y).
+pub fn apply_endo(pt: &BigPoint, beta: &BigUint, p: &BigUint) -> BigPoint {
+    if pt.infinity {
+        return BigPoint::infinity();
+    }
+    BigPoint::new(mod_mul(beta, &pt.x, p), pt.y.clone())
+}
+
+/// Empareja beta (mod p) y lambda (mod n) de modo que phi(G) = [lambda]G.
+///
+/// `find_beta` y `find_lambda` devuelven cada uno una de las dos raices
+/// primitivas; si no corresponden entre si, se sustituye beta por beta^2.
+pub fn matched_beta_lambda(g: &BigPoint, p: &BigUint, n: &BigUint) -> (BigUint, BigUint) {
+    let beta = find_beta(p);
+    let lambda = find_lambda(n);
+    let lambda_g = scalar_mul(&lambda, g, p);
+    if apply_endo(g, &beta, p) == lambda_g {
+        (beta, lambda)
+    } else {
+        (mod_mul(&beta, &beta, p), lambda)
+    }
+}
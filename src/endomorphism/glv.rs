@@ -0,0 +1,166 @@
+/// Metodo GLV: la cara constructiva del endomorfismo.
+///
+/// Como phi(P) = [lambda]P, se descompone k = k1 + k2*lambda (mod n) con
+/// |k1|, |k2| ≈ sqrt(n) y se calcula k1*P + k2*phi(P) con el truco de
+/// Straus-Shamir: los doblados se reparten entre ambos escalares, asi que
+/// hacen falta la mitad. La base corta del reticulo
+/// {(a, b) : a + b*lambda ≡ 0 (mod n)} sale del algoritmo de Euclides
+/// extendido sobre (n, lambda) (Guide to ECC, alg. 3.74).
+
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+use num_traits::Zero;
+
+use crate::math::curve::*;
+use crate::math::fp::*;
+use crate::math::jacobian::Jacobian;
+use crate::math::msm::msm;
+use crate::math::wnaf::MulCost;
+
+use super::apply_endo_affine;
+
+/// Base reducida v1 = (a1, b1), v2 = (a2, b2) del reticulo de lambda.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GlvBasis {
+    pub a1: BigInt,
+    pub b1: BigInt,
+    pub a2: BigInt,
+    pub b2: BigInt,
+    n: BigInt,
+}
+
+impl GlvBasis {
+    /// Euclides extendido sobre (n, lambda): cada resto cumple
+    /// r_i = s_i*n + t_i*lambda, asi que (r_i, -t_i) esta en el reticulo.
+    /// Con l el ultimo indice con r_l >= sqrt(n), v1 = (r_{l+1}, -t_{l+1}) y
+    /// v2 es el mas corto de (r_l, -t_l) y (r_{l+2}, -t_{l+2}).
+    pub fn new(n: &BigUint, lambda: &BigUint) -> Self {
+        let bound = n.sqrt();
+        let (mut r0, mut r1) = (BigInt::from(n.clone()), BigInt::from(lambda % n));
+        let (mut t0, mut t1) = (BigInt::zero(), BigInt::from(1));
+        while r1.magnitude() >= &bound {
+            let q = &r0 / &r1;
+            let r2 = &r0 - &q * &r1;
+            let t2 = &t0 - &q * &t1;
+            (r0, r1, t0, t1) = (r1, r2, t1, t2);
+        }
+        // ahora r0 = r_l (>= sqrt(n)) y r1 = r_{l+1} (< sqrt(n))
+        let q = &r0 / &r1;
+        let (r2, t2) = (&r0 - &q * &r1, &t0 - &q * &t1);
+
+        let norm = |a: &BigInt, b: &BigInt| a * a + b * b;
+        let (a2, b2) = if norm(&r0, &t0) <= norm(&r2, &t2) { (r0, -t0) } else { (r2, -t2) };
+        GlvBasis { a1: r1, b1: -t1, a2, b2, n: BigInt::from(n.clone()) }
+    }
+
+    /// k = k1 + k2*lambda (mod n) con |k1|, |k2| del orden de sqrt(n):
+    /// se resta de (k, 0) el vector del reticulo mas cercano (redondeo de Babai).
+    pub fn decompose(&self, k: &BigUint) -> (BigInt, BigInt) {
+        let k = BigInt::from(k.clone());
+        // round(x / n) = floor((2x + n) / 2n)
+        let round = |x: BigInt| {
+            let num: BigInt = x * 2 + &self.n;
+            num.div_floor(&(&self.n * 2))
+        };
+        let c1 = round(&self.b2 * &k);
+        let c2 = round(-&self.b1 * &k);
+        let k1 = k - &c1 * &self.a1 - &c2 * &self.a2;
+        let k2 = -(&c1 * &self.b1) - &c2 * &self.b2;
+        (k1, k2)
+    }
+}
+
+/// Magnitud de un escalar con signo en limbs u64 little-endian.
+pub fn signed_limbs(k: &BigInt) -> (bool, Vec<u64>) {
+    (k.sign() == Sign::Minus, k.magnitude().to_u64_digits())
+}
+
+/// k1*P1 + k2*P2 por Straus-Shamir: un solo bucle de doblados sobre los bits
+/// de ambos escalares, sumando P1, P2 o P1 + P2 segun la columna.
+pub fn shamir_mul<F: Field>(
+    k1: &[u64], p1: Option<(F, F)>,
+    k2: &[u64], p2: Option<(F, F)>,
+) -> Option<(F, F)> {
+    let like = p1.or(p2)?.0;
+    let both = affine_add(p1, p2);
+    let table = [None, p1, p2, both];
+    let bit = |k: &[u64], i: usize| k.get(i / 64).map_or(0, |&l| ((l >> (i % 64)) & 1) as usize);
+
+    let mut acc = Jacobian::identity(&like);
+    for i in (0..k1.len().max(k2.len()) * 64).rev() {
+        acc = acc.double();
+        let idx = bit(k1, i) | (bit(k2, i) << 1);
+        if idx != 0 {
+            acc = acc.add_affine(table[idx]);
+        }
+    }
+    acc.to_affine()
+}
+
+/// Operaciones que hace `shamir_mul`: P1 + P2, un doblado por columna salvo
+/// la primera y una suma por columna no nula salvo la primera.
+pub fn shamir_cost(k1: &[u64], k2: &[u64]) -> MulCost {
+    let len = |k: &[u64]| k.iter().rposition(|&l| l != 0).map_or(0, |i| i * 64 + 64 - k[i].leading_zeros() as usize);
+    let bits = len(k1).max(len(k2));
+    let bit = |k: &[u64], i: usize| k.get(i / 64).is_some_and(|&l| (l >> (i % 64)) & 1 == 1);
+    let columns = (0..bits).filter(|&i| bit(k1, i) || bit(k2, i)).count();
+    MulCost { doubles: bits.saturating_sub(1), adds: 1 + columns.saturating_sub(1) }
+}
+
+/// Descomposicion GLV de k sobre la curva toy (n < 2^63).
+pub fn glv_decompose(k: u64, n: u64, lambda: u64) -> (i64, i64) {
+    let basis = GlvBasis::new(&BigUint::from(n), &BigUint::from(lambda));
+    let (k1, k2) = basis.decompose(&BigUint::from(k % n));
+    let to_i64 = |x: BigInt| i64::try_from(x).expect("|k_i| ≈ sqrt(n) cabe en i64");
+    (to_i64(k1), to_i64(k2))
+}
+
+/// k*P = k1*P + k2*phi(P) en la curva toy, con phi(P) = (beta*x, y) = [lambda]P.
+/// P debe estar en el subgrupo de orden n, donde phi actua como lambda.
+pub fn glv_mul(k: u64, pt: &Point, n: u64, p: u64, beta: u64, lambda: u64) -> Point {
+    let (k1, k2) = glv_decompose(k, n, lambda);
+    let p1 = pt.to_fp(p);
+    let p2 = apply_endo_affine(p1, Fp::new(beta, p));
+    let signed = |k: i64, q: Option<(Fp, Fp)>| if k < 0 { q.map(|(x, y)| (x, -y)) } else { q };
+    Point::from_fp(shamir_mul(
+        &[k1.unsigned_abs()], signed(k1, p1),
+        &[k2.unsigned_abs()], signed(k2, p2),
+    ))
+}
+
+/// Coste de `glv_mul(k, ...)` en operaciones de grupo.
+pub fn glv_cost(k: u64, n: u64, lambda: u64) -> MulCost {
+    let (k1, k2) = glv_decompose(k, n, lambda);
+    shamir_cost(&[k1.unsigned_abs()], &[k2.unsigned_abs()])
+}
+
+/// sum_i k_i * P_i plegando el endomorfismo: cada termino se parte en
+/// k_i1 * P_i + k_i2 * phi(P_i), con el doble de puntos y escalares de la
+/// mitad de bits, asi que la cadena de doblados de Straus (o las ventanas
+/// de Pippenger) se reduce a la mitad.
+pub fn glv_msm<F: Field>(
+    basis: &GlvBasis, beta: F,
+    scalars: &[BigUint], points: &[Option<(F, F)>],
+) -> Option<(F, F)> {
+    assert_eq!(scalars.len(), points.len(), "msm: escalares y puntos no coinciden");
+    let mut halves = Vec::with_capacity(2 * scalars.len());
+    let mut pts = Vec::with_capacity(2 * points.len());
+    let signed = |neg: bool, q: Option<(F, F)>| if neg { q.map(|(x, y)| (x, -y)) } else { q };
+    for (k, pt) in scalars.iter().zip(points) {
+        let (k1, k2) = basis.decompose(k);
+        let ((neg1, k1), (neg2, k2)) = (signed_limbs(&k1), signed_limbs(&k2));
+        halves.push(k1);
+        pts.push(signed(neg1, *pt));
+        halves.push(k2);
+        pts.push(signed(neg2, apply_endo_affine(*pt, beta)));
+    }
+    msm(&halves, &pts)
+}
+
+/// `glv_msm` sobre la curva toy.
+pub fn glv_multi_mul(ks: &[u64], pts: &[Point], n: u64, p: u64, beta: u64, lambda: u64) -> Point {
+    let basis = GlvBasis::new(&BigUint::from(n), &BigUint::from(lambda));
+    let scalars: Vec<BigUint> = ks.iter().map(|&k| BigUint::from(k % n)).collect();
+    let points: Vec<_> = pts.iter().map(|pt| pt.to_fp(p)).collect();
+    Point::from_fp(glv_msm(&basis, Fp::new(beta, p), &scalars, &points))
+}
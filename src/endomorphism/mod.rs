@@ -3,46 +3,84 @@
 /// En una curva y^2 = x^3 + b con D=-3, existe beta tal que beta^3 = 1 (mod p).
 /// El endomorfismo phi(x, y) = (beta*x, y) es un automorfismo del grupo.
 
-use crate::math::field::*;
+pub mod big;
+pub mod secp256k1;
+pub mod glv;
+pub mod subgroup;
+
+pub use glv::*;
+pub use subgroup::*;
+
+use crate::error::{Error, Result};
+use crate::math::fp::*;
 use crate::math::curve::*;
+use crate::math::field::*;
+use crate::math::primes::is_prime;
+use crate::math::jacobian::Jacobian;
 
 /// Encuentra beta: raiz cubica primitiva de 1 mod p.
 /// Requiere p ≡ 1 (mod 3).
 pub fn find_beta(p: u64) -> u64 {
-    assert!(p % 3 == 1, "p debe ser ≡ 1 (mod 3) para que exista beta");
+    try_find_beta(p).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Como `find_beta`, con error `NoCubeRootOfUnity` si p ≢ 1 (mod 3).
+pub fn try_find_beta(p: u64) -> Result<u64> {
+    if p % 3 != 1 {
+        return Err(Error::NoCubeRootOfUnity { p });
+    }
 
     // Buscamos g tal que g^((p-1)/3) != 1, luego beta = g^((p-1)/3)
     let exp = (p - 1) / 3;
     for g in 2..p {
-        let candidate = mod_pow(g, exp, p);
-        if candidate != 1 {
+        let candidate = Fp::new(g, p).pow(exp);
+        if candidate != candidate.one() {
             // candidate es una raiz cubica primitiva de 1
-            return candidate;
+            return Ok(candidate.value());
         }
     }
-    panic!("No se encontro beta");
+    Err(Error::NoCubeRootOfUnity { p })
 }
 
 /// Encuentra lambda: raiz de x^2 + x + 1 ≡ 0 (mod n).
 /// lambda es el escalar tal que phi(P) = [lambda]P.
 pub fn find_lambda(n: u64) -> u64 {
+    try_find_lambda(n).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Como `find_lambda`, con error `LambdaNotFound` si no hay raiz. De las
+/// dos raices devuelve la menor.
+pub fn try_find_lambda(n: u64) -> Result<u64> {
     // x^2 + x + 1 = 0 (mod n) => x = (-1 ± sqrt(1 - 4)) / 2 = (-1 ± sqrt(-3)) / 2
-    // Equivale a buscar por fuerza bruta en curva toy
+    if is_prime(n) && n > 3 {
+        let s = mod_sqrt(n - 3, n).ok_or(Error::LambdaNotFound { n })?;
+        let lambda = mod_mul(mod_sub(s, 1, n), mod_inv(2, n), n);
+        return Ok(lambda.min(n - 1 - lambda));
+    }
+    // n compuesto: sin raiz cuadrada directa, se recorre Z/n
     for x in 2..n {
-        let val = mod_add(mod_add(mod_mul(x, x, n), x, n), 1, n);
-        if val == 0 {
-            return x;
+        let x = Scalar::new(x, n);
+        if (x.square() + x + x.one()).is_zero() {
+            return Ok(x.value());
         }
     }
-    panic!("No se encontro lambda (n no es compatible con D=-3)");
+    Err(Error::LambdaNotFound { n })
 }
 
 /// Aplica el endomorfismo phi(P) = (beta * x, y).
 pub fn apply_endo(pt: &Point, beta: u64, p: u64) -> Point {
-    if pt.infinity {
-        return Point::infinity();
-    }
-    Point::new(mod_mul(beta, pt.x, p), pt.y)
+    Point::from_fp(apply_endo_affine(pt.to_fp(p), Fp::new(beta, p)))
+}
+
+/// phi(P) = (beta * x, y) en coordenadas afines, generico sobre el cuerpo F.
+pub fn apply_endo_affine<F: Field>(pt: Option<(F, F)>, beta: F) -> Option<(F, F)> {
+    pt.map(|(x, y)| (beta * x, y))
+}
+
+/// phi en coordenadas jacobianas: x = X/Z^2, asi que beta*x = (beta*X)/Z^2
+/// y basta escalar X, sin normalizar.
+pub fn apply_endo_jacobian<F: Field>(pt: &Jacobian<F>, beta: F) -> Jacobian<F> {
+    Jacobian { x: beta * pt.x, y: pt.y, z: pt.z }
 }
 
 /// Genera las 6 imagenes de P bajo el grupo de automorfismos Aut(E) para |D|=3.
@@ -51,7 +89,7 @@ pub fn automorphism_orbit(pt: &Point, beta: u64, p: u64) -> Vec<Point> {
     if pt.infinity {
         return vec![Point::infinity()];
     }
-    let beta2 = mod_mul(beta, beta, p);
+    let beta2 = Fp::new(beta, p).square().value();
 
     let p1 = *pt;                                              // [1]P
     let p2 = apply_endo(pt, beta, p);                          // [zeta3]P
@@ -63,6 +101,14 @@ pub fn automorphism_orbit(pt: &Point, beta: u64, p: u64) -> Vec<Point> {
     vec![p1, p2, p3, p4, p5, p6]
 }
 
+/// Escalares asociados a `automorphism_orbit`, en el mismo orden:
+/// sigma_i(P) = [s_i]P con s_i en {1, lambda, lambda^2, -1, -lambda, -lambda^2}.
+pub fn orbit_scalars(lambda: Scalar) -> [Scalar; 6] {
+    let one = lambda.one();
+    let lambda2 = lambda.square();
+    [one, lambda, lambda2, -one, -lambda, -lambda2]
+}
+
 /// Representante canonico de la clase de equivalencia bajo Aut(E).
 /// Devuelve el punto con la menor (x, y) lexicograficamente.
 pub fn canonical_rep(pt: &Point, beta: u64, p: u64) -> Point {
@@ -0,0 +1,66 @@
+/// Endomorfismo GLV de secp256k1 sobre la aritmetica de 4 limbs.
+///
+/// beta (mod p) y lambda (mod n) son el par estandar de la literatura:
+/// phi(x, y) = (beta*x, y) = [lambda](x, y) para todo punto de la curva.
+
+use std::sync::OnceLock;
+
+use num_bigint::BigUint;
+
+use crate::math::fp::Field;
+use crate::math::secp256k1::*;
+
+use super::apply_endo_affine;
+use super::glv::*;
+
+/// Raiz cubica primitiva de 1 mod p.
+pub const BETA: FieldElement = FieldElement::from_raw([
+    0xC1396C28719501EE, 0x9CF0497512F58995, 0x6E64479EAC3434E9, 0x7AE96A2B657C0710,
+]);
+
+/// Raiz de x^2 + x + 1 mod n asociada a BETA.
+pub const LAMBDA: Scalar = Scalar::from_raw([
+    0xDF02967C1B23BD72, 0x122E22EA20816678, 0xA5261C028812645A, 0x5363AD4CC05C30E0,
+]);
+
+/// Aplica el endomorfismo phi(P) = (beta * x, y).
+pub fn apply_endo(pt: &Affine) -> Affine {
+    apply_endo_affine(*pt, BETA)
+}
+
+/// Comprueba los invariantes GLV sobre P: beta^3 = 1, lambda^2 + lambda + 1 = 0
+/// y phi(P) = [lambda]P.
+pub fn check_glv(pt: &Affine) -> bool {
+    let beta_ok = BETA.square() * BETA == FieldElement::ONE;
+    let lambda_ok = (LAMBDA.square() + LAMBDA + Scalar::ONE).is_zero();
+    beta_ok && lambda_ok && apply_endo(pt) == scalar_mul(&LAMBDA, pt)
+}
+
+/// Base GLV de (n, LAMBDA), calculada en el primer uso.
+pub fn glv_basis() -> &'static GlvBasis {
+    static BASIS: OnceLock<GlvBasis> = OnceLock::new();
+    BASIS.get_or_init(|| GlvBasis::new(&limbs_to_biguint(&scalar::N), &LAMBDA.to_biguint()))
+}
+
+/// k1, k2 de ~128 bits con k = k1 + k2*lambda (mod n), en magnitud y signo.
+pub fn glv_split(k: &Scalar) -> ((bool, Vec<u64>), (bool, Vec<u64>)) {
+    let (k1, k2) = glv_basis().decompose(&k.to_biguint());
+    (signed_limbs(&k1), signed_limbs(&k2))
+}
+
+/// k*P = k1*P + k2*phi(P) por Straus-Shamir: ~128 doblados en lugar de 256.
+/// Tiempo variable: no usar con escalares secretos.
+pub fn glv_mul(k: &Scalar, pt: &Affine) -> Affine {
+    let ((neg1, k1), (neg2, k2)) = glv_split(k);
+    let p1 = if neg1 { point_neg(pt) } else { *pt };
+    let p2 = apply_endo(pt);
+    let p2 = if neg2 { point_neg(&p2) } else { p2 };
+    shamir_mul(&k1, p1, &k2, p2)
+}
+
+/// sum_i k_i * P_i con el endomorfismo plegado (`glv_msm`).
+/// Tiempo variable: no usar con escalares secretos.
+pub fn msm(scalars: &[Scalar], points: &[Affine]) -> Affine {
+    let scalars: Vec<BigUint> = scalars.iter().map(Scalar::to_biguint).collect();
+    glv_msm(glv_basis(), BETA, &scalars, points)
+}
@@ -0,0 +1,211 @@
+/// Validacion de puntos y pertenencia al subgrupo <G> de orden n.
+///
+/// Los ataques reciben Q desde fuera: si Q no esta en la curva o no esta en
+/// <G>, BSGS termina sin solucion y Rho no termina nunca. `validate_point`
+/// comprueba que Q esta en la curva, no es O y pertenece a <G>, y devuelve
+/// un `ValidatedPoint`, el tipo que exigen los ataques.
+///
+/// `Subgroup::try_new` comprueba el cofactor. Exige n ∤ h: si n | h, [n]P = O
+/// no distingue <G> del resto de la n-torsion. Para #E = h*n, si n > 4 sqrt(p)
+/// hay un solo multiplo de n en el intervalo de Hasse y basta con que h*n
+/// caiga en el; si no, se compara con #E por Schoof. Con un h erroneo las
+/// pruebas de abajo aceptarian puntos fuera de <G>.
+///
+/// Pertenencia a <G> con #E = h*n y n ∤ h:
+/// - h = 1: basta con estar en la curva.
+/// - j = 0: con un vector corto (a, b) del reticulo GLV (a + b*lambda ≡ 0
+///   mod n) se comprueba [a]P + [b]phi(P) = O, escalares de la mitad de
+///   bits por Straus-Shamir, en lugar de [n]P = O. El nucleo de a + b*phi
+///   tiene a^2 - ab + b^2 puntos; si ese numero es coprimo con h, los unicos
+///   puntos racionales que pasan la prueba son los de <G>.
+/// - En otro caso, [n]P = O.
+
+use num_bigint::BigUint;
+use num_integer::Integer;
+
+use crate::error::{Error, PointError, Result};
+use crate::math::curve::*;
+use crate::math::fp::*;
+use crate::math::primes::prime_factors;
+use crate::math::schoof::schoof_count;
+use crate::math::weierstrass::Curve;
+
+use super::{apply_endo, apply_endo_affine, try_find_beta, try_find_lambda};
+use super::glv::{shamir_mul, GlvBasis};
+
+/// Subgrupo <G> de orden n en una curva con #E = cofactor * n.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Subgroup {
+    pub curve: Curve,
+    pub g: Point,
+    pub n: u64,
+    pub cofactor: u64,
+    /// (beta, lambda) con phi(G) = [lambda]G, o por que no existe.
+    endo: Result<(u64, u64)>,
+    /// (a, b) del reticulo GLV si la prueba por endomorfismo es valida.
+    short: Option<(i64, i64)>,
+}
+
+/// Punto de la curva, distinto de O y en el subgrupo contra el que se valido.
+/// Guarda (curva, G, n) de ese subgrupo: los ataques rechazan un punto
+/// validado contra otro (ver `Subgroup::member`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValidatedPoint {
+    point: Point,
+    curve: Curve,
+    g: Point,
+    n: u64,
+}
+
+impl ValidatedPoint {
+    pub fn point(&self) -> &Point {
+        &self.point
+    }
+}
+
+/// (beta, lambda) emparejados: de las dos raices de x^2 + x + 1 mod n, la
+/// que cumple phi(G) = [lambda]G.
+fn matched_endomorphism(curve: &Curve, g: &Point, n: u64) -> Result<(u64, u64)> {
+    if curve.a != 0 {
+        return Err(Error::NoEndomorphism);
+    }
+    let beta = try_find_beta(curve.p)?;
+    let lambda = try_find_lambda(n)?;
+    let phi_g = apply_endo(g, beta, curve.p);
+    [lambda, n - 1 - lambda]
+        .into_iter()
+        .find(|&l| curve.scalar_mul(l, g) == phi_g)
+        .map(|l| (beta, l))
+        .ok_or(Error::LambdaNotFound { n })
+}
+
+/// #E = h * n, sabiendo que n | #E (G tiene orden n).
+fn cofactor_matches(curve: &Curve, n: u64, h: u64) -> bool {
+    let (p, total) = (curve.p as i128, n as i128 * h as i128);
+    if total > 2 * p + 2 || (total - p - 1).pow(2) > 4 * p {
+        return false;
+    }
+    // n > 4 sqrt(p): #E es el unico multiplo de n en el intervalo de Hasse
+    if (n as i128).pow(2) > 16 * p {
+        return true;
+    }
+    schoof_count(curve) == total as u128
+}
+
+impl Subgroup {
+    /// Subgrupo generado por G (ver `try_new`).
+    pub fn new(curve: Curve, g: Point, n: u64, cofactor: u64) -> Self {
+        Self::try_new(curve, g, n, cofactor).unwrap_or_else(|e| panic!("subgrupo: {}", e))
+    }
+
+    /// Comprueba que G esta en la curva, que tiene orden exactamente n y que
+    /// #E = cofactor * n con n ∤ cofactor (error `WrongCofactor` si no: con
+    /// n | h hay puntos de orden n fuera de <G>).
+    pub fn try_new(curve: Curve, g: Point, n: u64, cofactor: u64) -> Result<Self> {
+        if g.infinity {
+            return Err(Error::PointAtInfinity);
+        }
+        if !curve.is_on_curve(&g) {
+            return Err(Error::PointNotOnCurve);
+        }
+        let exact = curve.scalar_mul(n, &g).infinity
+            && prime_factors(n).into_iter().all(|q| !curve.scalar_mul(n / q, &g).infinity);
+        if !exact {
+            return Err(Error::NoGenerator { order: n });
+        }
+        if cofactor.is_multiple_of(n) || !cofactor_matches(&curve, n, cofactor) {
+            return Err(Error::WrongCofactor { cofactor, order: n });
+        }
+        let endo = matched_endomorphism(&curve, &g, n);
+        let short = match endo {
+            Ok((_, lambda)) if cofactor > 1 => {
+                let basis = GlvBasis::new(&BigUint::from(n), &BigUint::from(lambda));
+                let a = i64::try_from(basis.a1).expect("|a| ≈ sqrt(n) cabe en i64");
+                let b = i64::try_from(basis.b1).expect("|b| ≈ sqrt(n) cabe en i64");
+                let (a128, b128) = (a as i128, b as i128);
+                let norm = (a128 * a128 - a128 * b128 + b128 * b128) as u128;
+                (norm.gcd(&(cofactor as u128)) == 1).then_some((a, b))
+            }
+            _ => None,
+        };
+        Ok(Subgroup { curve, g, n, cofactor, endo, short })
+    }
+
+    /// G como punto validado.
+    pub fn generator(&self) -> ValidatedPoint {
+        self.validated(self.g)
+    }
+
+    fn validated(&self, point: Point) -> ValidatedPoint {
+        ValidatedPoint { point, curve: self.curve, g: self.g, n: self.n }
+    }
+
+    /// Punto de Q si se valido contra este subgrupo; error `NotInSubgroup`
+    /// si se valido contra otro.
+    pub fn member<'a>(&self, q: &'a ValidatedPoint) -> Result<&'a Point> {
+        if (q.curve, q.g, q.n) != (self.curve, self.g, self.n) {
+            return Err(Error::NotInSubgroup { order: self.n });
+        }
+        Ok(&q.point)
+    }
+
+    /// (beta, lambda) con phi(G) = [lambda]G; error `NoEndomorphism`,
+    /// `NoCubeRootOfUnity` o `LambdaNotFound` si la curva no lo admite.
+    pub fn endomorphism(&self) -> Result<(u64, u64)> {
+        self.endo.clone()
+    }
+
+    /// true si la pertenencia se decide con [a]P + [b]phi(P) = O.
+    pub fn has_fast_check(&self) -> bool {
+        self.short.is_some()
+    }
+
+    /// P en <G>, para P en la curva y distinto de O.
+    fn contains_on_curve(&self, pt: &Point) -> bool {
+        if self.cofactor == 1 {
+            return true;
+        }
+        match (&self.endo, self.short) {
+            (Ok((beta, _)), Some((a, b))) => {
+                let p = self.curve.p;
+                let p1 = pt.to_fp(p);
+                let p2 = apply_endo_affine(p1, Fp::new(*beta, p));
+                let signed = |k: i64, q: Option<(Fp, Fp)>| if k < 0 { q.map(|(x, y)| (x, -y)) } else { q };
+                shamir_mul(
+                    &[a.unsigned_abs()], signed(a, p1),
+                    &[b.unsigned_abs()], signed(b, p2),
+                ).is_none()
+            }
+            _ => self.curve.scalar_mul(self.n, pt).infinity,
+        }
+    }
+
+    /// Misma prueba que `validate_point`, sin distinguir el motivo del rechazo.
+    pub fn contains(&self, pt: &Point) -> bool {
+        validate_point(self, pt).is_ok()
+    }
+
+    /// Pertenencia por [n]P = O, sin endomorfismo (referencia para `contains`).
+    pub fn contains_full(&self, pt: &Point) -> bool {
+        !pt.infinity && self.curve.is_on_curve(pt) && self.curve.scalar_mul(self.n, pt).infinity
+    }
+}
+
+/// Valida P contra el subgrupo <G> de orden n: error `AtInfinity` si P = O,
+/// `NotOnCurve` si no satisface la ecuacion de la curva y `NotInSubgroup`
+/// si no esta en <G>.
+pub fn validate_point(
+    sub: &Subgroup,
+    pt: &Point,
+) -> std::result::Result<ValidatedPoint, PointError> {
+    if pt.infinity {
+        return Err(PointError::AtInfinity);
+    }
+    if !sub.curve.is_on_curve(pt) {
+        return Err(PointError::NotOnCurve);
+    }
+    if !sub.contains_on_curve(pt) {
+        return Err(PointError::NotInSubgroup { order: sub.n });
+    }
+    Ok(sub.validated(*pt))
+}
@@ -0,0 +1,108 @@
+/// Errores de la capa matematica y de los ataques.
+///
+/// Las funciones `try_*` devuelven `Result` en lugar de abortar, para poder
+/// recorrer miles de juegos de parametros sin detener el proceso. Las
+/// versiones sin prefijo siguen existiendo y hacen panic con el mismo
+/// mensaje.
+
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// `value` no es invertible modulo `modulus`: gcd(value, modulus) = gcd > 1
+    /// (o value ≡ 0, con gcd = modulus).
+    NotInvertible { value: u64, modulus: u64, gcd: u64 },
+    /// No existe raiz cubica primitiva de 1 mod p (requiere p ≡ 1 mod 3).
+    NoCubeRootOfUnity { p: u64 },
+    /// x^2 + x + 1 no tiene raiz modulo n.
+    LambdaNotFound { n: u64 },
+    /// Ningun punto de la curva tiene orden exactamente `order`.
+    NoGenerator { order: u64 },
+    /// El punto no satisface la ecuacion de la curva.
+    PointNotOnCurve,
+    /// Se esperaba un punto finito y se recibio O.
+    PointAtInfinity,
+    /// #E no es `cofactor` * `order`, o `order` divide a `cofactor`.
+    WrongCofactor { cofactor: u64, order: u64 },
+    /// El punto esta en la curva pero no en el subgrupo de orden `order`.
+    NotInSubgroup { order: u64 },
+    /// La curva no tiene el endomorfismo phi(x, y) = (beta x, y) (requiere a = 0).
+    NoEndomorphism,
+    /// Bytes que no forman una codificacion SEC1 valida (prefijo, longitud
+    /// o coordenada fuera de rango).
+    InvalidEncoding { reason: &'static str },
+    /// expand_message_xmd no puede producir `len` bytes (maximo `max`).
+    OutputTooLong { len: usize, max: usize },
+    /// El intervalo de Hasse de p no cabe en u64.
+    HasseOverflow { p: u64 },
+    /// El intervalo [lo, lo + width) de BSGS no cabe en u64.
+    IntervalOverflow { lo: u64, width: u64 },
+    /// El algoritmo termino sin encontrar el logaritmo discreto.
+    NoSolution,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::NotInvertible { value, modulus, gcd } => {
+                write!(f, "{} no es invertible mod {} (gcd = {})", value, modulus, gcd)
+            }
+            Error::NoCubeRootOfUnity { p } => {
+                write!(f, "no existe raiz cubica primitiva de 1 mod {} (p ≢ 1 mod 3)", p)
+            }
+            Error::LambdaNotFound { n } => {
+                write!(f, "no se encontro lambda mod {} (n no es compatible con D=-3)", n)
+            }
+            Error::NoGenerator { order } => write!(f, "no se encontro generador de orden {}", order),
+            Error::PointNotOnCurve => write!(f, "el punto no esta en la curva"),
+            Error::PointAtInfinity => write!(f, "el punto es el punto en el infinito"),
+            Error::WrongCofactor { cofactor, order } => {
+                write!(f, "cofactor {} invalido para n = {} (#E != h * n o n | h)", cofactor, order)
+            }
+            Error::NotInSubgroup { order } => write!(f, "el punto no esta en el subgrupo de orden {}", order),
+            Error::NoEndomorphism => write!(f, "la curva no tiene endomorfismo GLV (requiere a = 0)"),
+            Error::InvalidEncoding { reason } => write!(f, "codificacion SEC1 invalida: {}", reason),
+            Error::OutputTooLong { len, max } => {
+                write!(f, "expand_message_xmd: {} bytes pedidos, maximo {}", len, max)
+            }
+            Error::HasseOverflow { p } => write!(f, "el intervalo de Hasse de p = {} no cabe en u64", p),
+            Error::IntervalOverflow { lo, width } => {
+                write!(f, "el intervalo [{}, {} + {}) no cabe en u64", lo, lo, width)
+            }
+            Error::NoSolution => write!(f, "no se encontro solucion"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Motivo por el que `validate_point` rechaza un punto.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PointError {
+    /// Se recibio O.
+    AtInfinity,
+    /// El punto no satisface la ecuacion de la curva.
+    NotOnCurve,
+    /// El punto esta en la curva pero no en el subgrupo de orden `order`.
+    NotInSubgroup { order: u64 },
+}
+
+impl fmt::Display for PointError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Error::from(*self).fmt(f)
+    }
+}
+
+impl std::error::Error for PointError {}
+
+impl From<PointError> for Error {
+    fn from(e: PointError) -> Self {
+        match e {
+            PointError::AtInfinity => Error::PointAtInfinity,
+            PointError::NotOnCurve => Error::PointNotOnCurve,
+            PointError::NotInSubgroup { order } => Error::NotInSubgroup { order },
+        }
+    }
+}
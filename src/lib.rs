@@ -1,4 +1,6 @@
+pub mod error;
 pub mod math;
 pub mod endomorphism;
 pub mod attacks;
 pub mod formal_verification;
+pub mod protocols;
@@ -3,14 +3,37 @@
 /// Curva: y^2 = x^3 + 7 sobre F_p (misma ecuación que secp256k1)
 /// Primo toy: p = 10477 (p ≡ 1 mod 3, necesario para D=-3)
 
+use std::time::Instant;
+
+use num_bigint::BigUint;
+
 use d3_symmetry::math::*;
 use d3_symmetry::endomorphism::*;
+use d3_symmetry::math::big;
+use d3_symmetry::endomorphism::big as big_endo;
+use d3_symmetry::math::secp256k1 as k1;
+use d3_symmetry::endomorphism::secp256k1 as k1_endo;
 use d3_symmetry::attacks::*;
 use d3_symmetry::formal_verification::Z3Verifier;
+use d3_symmetry::protocols;
+
+/// Semilla por defecto de los caminos de Rho y del experimento estadistico.
+const DEFAULT_SEED: u64 = 0xD3;
+
+/// Ensayos del experimento estadistico [12].
+const TRIALS: usize = 200;
+
+/// Primos l del informe de torsion racional en [3].
+const TORSION_BOUND: u64 = 7;
 
 fn main() {
     println!("=== D3-Symmetry: Endomorfismo GLV en curvas con D=-3 ===\n");
 
+    // Semilla: `cargo run -- <semilla>`. Sin argumento la clave del recorrido
+    // es la fija k = 7777 (la de docs/Prueba_Visual.md); con semilla, aleatoria.
+    let seed_arg = std::env::args().nth(1).map(|s| s.parse::<u64>().expect("semilla: entero u64"));
+    let mut rng = Rng::new(seed_arg.unwrap_or(DEFAULT_SEED));
+
     // ── Parametros de la curva toy ──
     let p: u64 = 10477;  // primo, 10477 mod 3 == 1, orden del grupo primo
     let b: u64 = 7;      // y^2 = x^3 + 7 (como Bitcoin)
@@ -21,9 +44,22 @@ fn main() {
     println!("[1] CURVA TOY (análoga a secp256k1)");
     println!("    Ecuacion: y^2 = x^3 + 7");
     println!("    Campo:    F_{{{}}}   (primo, {} mod 3 = {})", p, p, p % 3);
+    assert!(is_prime(p), "p no es primo!");
 
     let order = count_points(b, p);
     println!("    Orden del grupo: n = {}", order);
+    assert_eq!(count_points_cm(b, p), Some(order), "conteo CM discrepa!");
+    println!("    Verificacion: n por Cornacchia + residuo sextico de b ✓");
+    let mut cm_cases = 0;
+    for q in (7..500u64).filter(|&q| q % 3 == 1 && is_prime(q)) {
+        for bq in 1..=24u64.min(q - 1) {
+            assert_eq!(count_points_cm(bq, q), Some(count_points(bq, q)), "CM con p = {}, b = {}", q, bq);
+            cm_cases += 1;
+        }
+    }
+    println!("    Verificacion: CM = fuerza bruta en {} curvas (p ≡ 1 mod 3, p < 500) ✓", cm_cases);
+    assert!(is_prime(order), "n no es primo: {:?}", factor(order));
+    println!("    Verificacion: p y n primos (Miller-Rabin determinista) ✓");
 
     let g = find_generator(b, p, order);
     println!("    Generador: G = {}", g);
@@ -33,7 +69,43 @@ fn main() {
     // Verificar que G tiene orden n
     let check = scalar_mul(order, &g, p);
     assert!(check.infinity, "[n]G != O");
-    println!("    Verificacion: [n]G = O ✓\n");
+    println!("    Verificacion: [n]G = O ✓");
+
+    // #E(F_{p^k}) por la recurrencia de la traza frente a fuerza bruta con lift_x
+    for q in [5u64, 7, 11, 13, 17, 19, 23, 29] {
+        let nr = quadratic_non_residue(q).expect("q impar");
+        for bq in 1..=4u64 {
+            let mut brute = 1u64;
+            for c0 in 0..q {
+                for c1 in 0..q {
+                    if let Some((_, y)) = lift_x(Fp2::new(c0, c1, nr, q), bq) {
+                        brute += if y.is_zero() { 1 } else { 2 };
+                    }
+                }
+            }
+            assert_eq!(count_points_extension(bq, q, 2), BigUint::from(brute),
+                       "#E(F_{{{}^2}}) con b = {}", q, bq);
+        }
+    }
+    for q in [5u64, 7, 11] {
+        let zero = Fpk::<3>::with_default_modulus(q);
+        let mut brute = 1u64;
+        for c in 0..q * q * q {
+            if let Some((_, y)) = lift_x(zero.with([c % q, c / q % q, c / (q * q)]), 1) {
+                brute += if y.is_zero() { 1 } else { 2 };
+            }
+        }
+        assert_eq!(count_points_extension(1, q, 3), BigUint::from(brute), "#E(F_{{{}^3}})", q);
+    }
+    println!("    #E(F_p^2) y #E(F_p^3) por la traza = fuerza bruta sobre Fp2 / Fpk (8 primos x 4 b) ✓");
+
+    // Grado de inmersion: supersingular (p ≡ 2 mod 3, #E = p + 1) frente a la curva toy
+    let p_ss = 1019u64;
+    assert_eq!(count_points(b, p_ss), p_ss + 1, "j = 0 con p ≡ 2 (mod 3) es supersingular");
+    assert_eq!(embedding_degree(17, p_ss, 64), Some(2), "17 | p + 1 => k = 2");
+    assert_eq!(count_points_extension(b, p_ss, 2), BigUint::from((p_ss + 1) * (p_ss + 1)));
+    assert_eq!(embedding_degree(order, p, 64), None, "curva toy con k pequeno");
+    println!("    Grado de inmersion: k = 2 en y^2 = x^3 + 7 sobre F_{} (MOV), k > 64 en la toy ✓\n", p_ss);
 
     // ════════════════════════════════════════════════
     // [2] ENDOMORFISMO (la debilidad de |D|=3)
@@ -61,6 +133,32 @@ fn main() {
     println!("    Verificacion: lambda^2 + lambda + 1 mod n = {} ✓\n",
              mod_add(mod_add(mod_mul(lambda, lambda, order), lambda, order), 1, order));
 
+    // Cara constructiva: k*G = k1*G + k2*phi(G) con |k1|, |k2| ≈ sqrt(n)
+    println!("    GLV constructivo (descomposicion + Straus-Shamir):");
+    let k_glv = order / 2 + 1234;
+    let (k1_glv, k2_glv) = glv_decompose(k_glv, order, lambda);
+    println!("    k = {} = {} + ({})*lambda (mod n)", k_glv, k1_glv, k2_glv);
+    assert_eq!(glv_mul(k_glv, &g, order, p, beta, lambda), scalar_mul(k_glv, &g, p),
+               "GLV discrepa de scalar_mul");
+    let naf = wnaf_cost(&[k_glv], wnaf_window(64 - k_glv.leading_zeros()));
+    let glv = glv_cost(k_glv, order, lambda);
+    println!("    scalar_mul (NAF): {} doblados + {} sumas = {}", naf.doubles, naf.adds, naf.total());
+    println!("    GLV:              {} doblados + {} sumas = {}", glv.doubles, glv.adds, glv.total());
+    println!("    Verificacion: k1*G + k2*phi(G) = k*G ✓");
+
+    // Multiplicacion multiescalar (Straus por debajo de PIPPENGER_THRESHOLD, Pippenger
+    // por encima) frente a la suma de scalar_mul
+    for batch in [4usize, PIPPENGER_THRESHOLD] {
+        let pts: Vec<Point> = (0..batch as u64).map(|i| scalar_mul(i + 2, &g, p)).collect();
+        let ks: Vec<u64> = (0..batch as u64).map(|i| (order / (i + 3) + 17 * i) % order).collect();
+        let expected = ks.iter().zip(&pts)
+            .fold(Point::infinity(), |acc, (&k, pt)| point_add(&acc, &scalar_mul(k, pt, p), p));
+        assert_eq!(multi_scalar_mul(&ks, &pts, p), expected, "MSM con {} puntos", batch);
+        assert_eq!(glv_multi_mul(&ks, &pts, order, p, beta, lambda), expected,
+                   "MSM GLV con {} puntos", batch);
+    }
+    println!("    MSM (Straus y Pippenger, con y sin GLV) = suma de scalar_mul ✓\n");
+
     // ════════════════════════════════════════════════
     // [3] GRUPO DE AUTOMORFISMOS
     // ════════════════════════════════════════════════
@@ -75,21 +173,114 @@ fn main() {
                  if on_curve { "✓" } else { "✗" });
         assert!(on_curve, "{} no esta en la curva!", labels[i]);
     }
-    println!("    Las 6 imagenes estan en la curva ✓\n");
+    println!("    Las 6 imagenes estan en la curva ✓");
+
+    // Las 3 abscisas con ordenada Gy son las raices cubicas de Gy^2 - b: G, [zeta3]G, [zeta3^2]G
+    let mut same_y = orbit[..3].to_vec();
+    same_y.sort_by_key(|pt| pt.x);
+    assert_eq!(points_with_y(g.y, b, p), same_y, "raices cubicas de Gy^2 - b");
+    println!("    x^3 = Gy^2 - b tiene 3 raices: exactamente G, [zeta3]G, [zeta3^2]G ✓");
+
+    // Raices r-esimas (r | p - 1 por Adleman-Manders-Miller, r ∤ p - 1 por inversion
+    // del exponente) y raices cuadradas en F_p^2 de no-residuos de F_p
+    let nr = quadratic_non_residue(p).expect("p impar");
+    for r in [2u64, 3, 5, 7, 97] {
+        for x in [2u64, g.x, g.y, p - 1] {
+            let a = mod_pow(x, r, p);
+            let root = mod_rth_root(a, r, p).expect("a = x^r es potencia r-esima");
+            assert_eq!(mod_pow(root, r, p), a, "raiz {}-esima", r);
+        }
+        if (p - 1).is_multiple_of(r) {
+            let non_power = (2..p).find(|&z| mod_pow(z, (p - 1) / r, p) != 1).expect("r | p - 1");
+            assert_eq!(mod_rth_root(non_power, r, p), None, "no potencia {}-esima", r);
+        }
+    }
+    for r in [0u64, 1, 4, 9] {
+        assert_eq!(mod_rth_root(4, r, p), None, "r = {} no es primo", r);
+    }
+    let z = Fp2::new(nr, 0, nr, p);
+    assert_eq!(z.sqrt().expect("F_p ⊂ (F_p^2)^2").square(), z, "sqrt de nr en F_p^2");
+    let w = Fp2::new(g.x, g.y, nr, p).square();
+    assert_eq!(w.sqrt().expect("w es cuadrado").square(), w, "sqrt en F_p^2");
+    println!("    Raices r-esimas (r = 2, 3, 5, 7, 97) y sqrt en F_p^2 de nr = {} ✓", nr);
+
+    // Twists sexticos: una clase de b en F_p* / (F_p*)^6 por cada twist
+    let twists = sextic_twists(b, p).expect("p ≡ 1 (mod 3)");
+    println!("    Twists sexticos de y^2 = x^3 + b (seguridad ante twists):");
+    for tw in twists.iter() {
+        let map = TwistMap::new(b, tw.b, p).expect("twist valido");
+        let image = map.apply(map.embed(&g));
+        assert!(is_on_curve_over(&image, tw.b), "imagen fuera del twist");
+        let structure = group_structure(tw.b, p, tw.order);
+        assert_eq!(structure.order(), tw.order, "estructura del twist");
+        println!("      b = {:>5}: #E = {:>5} ≅ {:<14} mayor primo {:>5}, isomorfo sobre F_p^{}",
+                 tw.b, tw.order, structure.to_string(), tw.largest_prime, map.degree);
+    }
+    assert_eq!(twists[0].order + twists[3].order, 2 * p + 2, "twist cuadratico");
+    println!("    G llevado a cada twist por (x, y) -> (delta^2 x, delta^3 y) ✓");
+
+    // l-torsion racional por raices de los polinomios de division
+    println!("    Torsion racional E(F_p)[l] (raices de psi_l en F_p):");
+    for tw in twists.iter() {
+        let report = torsion_report(tw.b, p, TORSION_BOUND);
+        for t in report.iter() {
+            assert!(tw.order.is_multiple_of(t.order()), "|E[{}]| no divide #E", t.l);
+        }
+        // ker(1 - phi) = {O, (0, ±sqrt(b))} ⊂ E[3], racional si b es cuadrado
+        let kernel = report.iter().find(|t| t.l == 3).expect("3 <= TORSION_BOUND");
+        let has_kernel = kernel.points.iter().any(|pt| pt.x == 0);
+        assert_eq!(has_kernel, mod_sqrt(tw.b, p).is_some(), "ker(1 - phi) en E[3]");
+        let cells: Vec<String> = report.iter().map(|t| format!("E[{}] = {}", t.l, t)).collect();
+        println!("      b = {:>5}: {}{}", tw.b, cells.join(", "),
+                 if has_kernel { "  (0, ±sqrt(b)) ∈ ker(1 - phi)" } else { "" });
+    }
+
+    // Validacion de puntos: con cofactor, estar en la curva no implica estar en <G>
+    let (tw_sub, tw_points) = twists.iter()
+        .filter(|tw| tw.order > tw.largest_prime)
+        .map(|tw| {
+            let ec = Curve::new(0, tw.b, p);
+            let (g_tw, n_tw, h_tw) = ec.prime_order_subgroup(tw.order).expect("orden por CM");
+            (Subgroup::new(ec, g_tw, n_tw, h_tw), tw.order)
+        })
+        .find(|(sub, _)| sub.has_fast_check())
+        .expect("algun twist con cofactor admite la prueba rapida");
+    let mut members = 0u64;
+    for x in 0..p {
+        let rhs = mod_add(mod_pow(x, 3, p), tw_sub.curve.b, p);
+        for y in mod_sqrt(rhs, p).into_iter().flat_map(|y| [y, mod_sub(0, y, p)]) {
+            let pt = Point::new(x, y);
+            assert_eq!(tw_sub.contains(&pt), tw_sub.contains_full(&pt), "prueba rapida discrepa");
+            members += tw_sub.contains(&pt) as u64;
+        }
+    }
+    assert_eq!(members + 1, tw_sub.n, "<G> del twist");
+    println!("    Twist b = {}: #E = {} = {} * {}; [a]P + [b]phi(P) = O acepta {} de {} puntos,",
+             tw_sub.curve.b, tw_points, tw_sub.cofactor, tw_sub.n, members, tw_points - 1);
+    println!("    igual que [n]P = O con la mitad de doblados ✓\n");
 
     // ════════════════════════════════════════════════
     // [4] ATAQUE ECDLP: BSGS ESTANDAR
     // ════════════════════════════════════════════════
     println!("[4] ECDLP: BSGS ESTANDAR (sin explotar endomorfismo)");
 
-    // Clave secreta determinista para reproducibilidad
-    let secret_k = 7777u64 % order;
+    // Clave secreta fija o derivada de la semilla, reproducible en ambos casos
+    let secret_k = match seed_arg {
+        Some(_) => rng.scalar(order),
+        None => 7777u64 % order,
+    };
     let pub_q = scalar_mul(secret_k, &g, p);
-    println!("    Clave secreta: k = {} (fijada para reproducibilidad)", secret_k);
+    match seed_arg {
+        Some(seed) => println!("    Clave secreta: k = {} (semilla {})", secret_k, seed),
+        None => println!("    Clave secreta: k = {} (fijada para reproducibilidad)", secret_k),
+    }
     println!("    Clave publica: Q = k*G = {}", pub_q);
+    let toy = Subgroup::new(Curve::new(0, b, p), g, order, 1);
+    let q_valid = validate_point(&toy, &pub_q).expect("Q esta en <G>");
+    println!("    Q validado: en la curva, distinto de O y en <G> ✓");
     println!("    Resolviendo Q = k*G por BSGS estandar...");
 
-    let (found_k, ops_std) = bsgs_standard(&pub_q, &g, order, p);
+    let (found_k, ops_std) = bsgs_standard(&toy, &q_valid);
     assert_eq!(found_k, secret_k, "BSGS estandar encontro k incorrecto!");
     let m_std = (order as f64).sqrt().ceil() as u64;
     println!("    Baby steps: {}, Giant steps: {}", m_std, ops_std as u64 - m_std);
@@ -103,7 +294,7 @@ fn main() {
     println!("    Misma Q, misma curva.");
     println!("    Usando clases de equivalencia de tamanio 6...");
 
-    let (found_k2, ops_glv) = bsgs_glv(&pub_q, &g, order, p, beta);
+    let (found_k2, ops_glv) = bsgs_glv(&toy, &q_valid);
     let verify = scalar_mul(found_k2, &g, p);
     assert_eq!(verify, pub_q, "BSGS GLV encontro k incorrecto!");
     let m_glv = ((order as f64) / 6.0).sqrt().ceil() as u64;
@@ -154,7 +345,7 @@ fn main() {
     println!("    Deteccion de ciclo: Floyd (tortuga/liebre)");
     println!("    Sin reduccion por automorfismos...");
 
-    let rho_std = pollard_rho_standard(&pub_q, &g, order, p);
+    let rho_std = pollard_rho_standard(&toy, &q_valid, rng.next_u64());
     let rho_verify = scalar_mul(rho_std.k, &g, p);
     assert_eq!(rho_verify, pub_q, "Pollard Rho estandar encontro k incorrecto!");
     println!("    Iteraciones: {}", rho_std.iterations);
@@ -162,7 +353,7 @@ fn main() {
 
     println!("    Con Equivalence Class Search (|Aut(E)| = 6)...");
 
-    let rho_glv = pollard_rho_glv(&pub_q, &g, order, p, beta);
+    let rho_glv = pollard_rho_glv(&toy, &q_valid, rng.next_u64());
     let rho_glv_verify = scalar_mul(rho_glv.k, &g, p);
     assert_eq!(rho_glv_verify, pub_q, "Pollard Rho GLV encontro k incorrecto!");
     println!("    Iteraciones: {}", rho_glv.iterations);
@@ -191,6 +382,310 @@ fn main() {
     println!("    La reduccion por clases de equivalencia bajo Aut(E) comprime");
     println!("    el espacio de colision. Factor teorico: sqrt(|Aut(E)|) = sqrt(6) = {:.2}x.", (6.0f64).sqrt());
 
+    // ════════════════════════════════════════════════
+    // [10] SECP256K1 REAL (backend BigUint)
+    // ════════════════════════════════════════════════
+    println!();
+    println!("[10] SECP256K1 REAL (backend BigUint, 256 bits)");
+
+    let curve = big::secp256k1();
+    assert!(big::is_on_curve(&curve.g, &curve.b, &curve.p), "G no esta en secp256k1!");
+    println!("    G esta en la curva ✓");
+    assert!(is_probable_prime_big(&curve.p, 32) && is_probable_prime_big(&curve.n, 32),
+            "p o n no son primos!");
+    // con 32 rondas las bases llegan a 131: primos por encima de la division de prueba
+    for q in [101u64, 103, 131] {
+        assert!(is_probable_prime_big(&BigUint::from(q), 32), "{} es primo", q);
+    }
+    assert!(!is_probable_prime_big(&BigUint::from(101u64 * 103), 32), "101 * 103 es compuesto");
+    println!("    p y n primos (Miller-Rabin, 32 rondas) ✓");
+    assert_eq!(big::count_points_cm(&curve.b, &curve.p), Some(curve.n.clone()), "#E != n");
+    println!("    #E(F_p) = n por multiplicacion compleja ✓");
+
+    let (beta_k1, lambda_k1) = big_endo::matched_beta_lambda(&curve.g, &curve.p, &curve.n);
+    println!("    beta   = {:#x}", beta_k1);
+    println!("    lambda = {:#x}", lambda_k1);
+    let phi_g_k1 = big_endo::apply_endo(&curve.g, &beta_k1, &curve.p);
+    assert_eq!(phi_g_k1, big::scalar_mul(&lambda_k1, &curve.g, &curve.p), "phi(G) != [lambda]G");
+    println!("    Verificacion: phi(G) = [lambda]G ✓");
+
+    // Clave de 24 bits escondida en un intervalo conocido de 256 bits
+    let lo = big::from_hex("8000000000000000000000000000000000000000000000000000000000000000");
+    let width = 1u64 << 24;
+    let secret_k1 = &lo + BigUint::from(0x00AB_CDEFu64 % width);
+    let pub_k1 = big::scalar_mul(&secret_k1, &curve.g, &curve.p);
+    println!("    Resolviendo Q = k*G con k en [2^255, 2^255 + 2^24)...");
+    let (found_k1, ops_k1) = bsgs_interval(&pub_k1, &curve.g, &lo, width, &curve.p)
+        .expect("k no encontrado en el intervalo");
+    assert_eq!(found_k1, secret_k1, "BSGS por intervalo encontro k incorrecto!");
+    println!("    Total operaciones: {}", ops_k1);
+    println!("    Resultado: k = {:#x} ✓", found_k1);
+
+    // Mismo calculo con la aritmetica dedicada de 4 limbs (p = 2^256 - 2^32 - 977)
+    println!();
+    println!("    Backend dedicado 4x64 (reduccion por la forma especial de p):");
+    assert!(k1_endo::check_glv(&k1::GENERATOR), "invariantes GLV no se cumplen");
+    println!("    beta^3 = 1, lambda^2 + lambda + 1 = 0, phi(G) = [lambda]G ✓");
+
+    let k_k1 = k1::Scalar::from_biguint(&big::from_hex(
+        "9F2C6A1E5B7D3048C1E9A2F47B6D8053E4A1C7B92D5F6038A7E1B4C9D2F50A63"));
+    assert_eq!(k1_endo::glv_mul(&k_k1, &k1::GENERATOR), k1::scalar_mul(&k_k1, &k1::GENERATOR),
+               "GLV discrepa de wNAF");
+    let ((_, half1), (_, half2)) = k1_endo::glv_split(&k_k1);
+    let naf = wnaf_cost(&k_k1.limbs(), 5);
+    let glv = shamir_cost(&half1, &half2);
+    let bits = |limbs: &[u64]| limbs.len() * 64 - limbs.last().map_or(64, |l| l.leading_zeros() as usize);
+    println!("    GLV: k = k1 + k2*lambda con k1, k2 de {} y {} bits", bits(&half1), bits(&half2));
+    println!("    wNAF (w=5): {} ops; GLV + Straus-Shamir: {} ops ({:.2}x) ✓",
+             naf.total(), glv.total(), naf.total() as f64 / glv.total() as f64);
+
+    let q_big = big::scalar_mul(&secret_k1, &curve.g, &curve.p);
+    let (qx, qy) = k1::scalar_mul(&k1::Scalar::from_biguint(&secret_k1), &k1::GENERATOR)
+        .expect("k*G != O");
+    assert_eq!((qx.to_biguint(), qy.to_biguint()), (q_big.x, q_big.y), "backends discrepan");
+    println!("    k*G identico en ambos backends ✓");
+
+    // Cadena de multiplicaciones en F_p: x <- x * Gx
+    let reps = 100_000u32;
+    let t0 = Instant::now();
+    let mut acc_big = curve.g.x.clone();
+    for _ in 0..reps {
+        acc_big = big::mod_mul(&acc_big, &curve.g.x, &curve.p);
+    }
+    let t_big = t0.elapsed();
+    let gx = k1::GENERATOR.expect("G != O").0;
+    let t0 = Instant::now();
+    let mut acc_fast = gx;
+    for _ in 0..reps {
+        acc_fast = acc_fast * gx;
+    }
+    let t_fast = t0.elapsed();
+    assert_eq!(acc_fast.to_biguint(), acc_big, "backends discrepan");
+    println!("    Tiempo por mod_mul: BigUint {:?}, 4x64 {:?} ({:.1}x)",
+             t_big / reps, t_fast / reps, t_big.as_secs_f64() / t_fast.as_secs_f64());
+
+    // Escalera de Montgomery con formulas completas: el flujo no depende de k
+    println!();
+    println!("    Modo tiempo constante (escalera de Montgomery, formulas completas):");
+    let k_ct = k1::Scalar::from_biguint(&secret_k1);
+    assert_eq!(k1::ct::scalar_mul_ct(&k_ct, &k1::GENERATOR), k1::scalar_mul(&k_ct, &k1::GENERATOR),
+               "escalera discrepa de double-and-add");
+    println!("    k*G por la escalera = k*G afin ✓");
+
+    let alice = k_ct;
+    let bob = k1::Scalar::from_u64(0xB0B) * k_ct + k1::Scalar::from_u64(3);
+    let q_alice = protocols::public_key(&alice).expect("clave nula");
+    let q_bob = protocols::public_key(&bob).expect("clave nula");
+    let s_alice = protocols::shared_secret(&alice, &q_bob).expect("ECDH invalido");
+    let s_bob = protocols::shared_secret(&bob, &q_alice).expect("ECDH invalido");
+    assert_eq!(s_alice, s_bob, "ECDH no coincide");
+    println!("    ECDH: secreto compartido = {} ✓", s_alice);
+
+    let z = k1::Scalar::from_u64(0x00C0FFEE);
+    let nonce = bob * bob;
+    let sig = protocols::sign(&alice, &z, &nonce).expect("nonce invalido");
+    assert!(protocols::verify(&q_alice, &z, &sig), "firma ECDSA invalida");
+    assert!(!protocols::verify(&q_bob, &z, &sig), "firma aceptada con otra clave");
+    println!("    ECDSA: firma verificada, rechazada con otra clave ✓");
+
+    // Codificacion SEC1: vector estandar de G comprimido (33 bytes)
+    let g_sec1 = big::from_hex("0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798").to_bytes_be();
+    let g_dec = big::decode_point(&g_sec1, &curve.b, &curve.p).expect("G SEC1 invalido");
+    assert_eq!(g_dec, curve.g, "G descomprimido discrepa");
+    let pub_sec1 = big::encode_point(&pub_k1, &curve.p, true);
+    assert_eq!(big::decode_point(&pub_sec1, &curve.b, &curve.p).ok(), Some(pub_k1.clone()), "ida y vuelta SEC1");
+    println!("    SEC1: G = 02 79BE...1798 descomprimido con mod_sqrt, Q en {} / {} bytes ✓",
+             pub_sec1.len(), big::encode_point(&pub_k1, &curve.p, false).len());
+
+    // Hash a curva (RFC 9380): SSWU en la curva 3-isogena + isogenia; SvdW en la toy
+    let q128 = format!("q128_{}", "q".repeat(128));
+    let a512 = format!("a512_{}", "a".repeat(512));
+    let h2c_msgs = ["", "abc", "abcdef0123456789", &q128, &a512];
+
+    // expand_message_xmd (RFC 9380, K.1): len_in_bytes = 0x20 y 0x80
+    let xmd_dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+    let xmd_vectors = [
+        ("68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235",
+         "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbee0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dcc541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced"),
+        ("d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615",
+         "abba86a6129e366fc877aab32fc4ffc70120d8996c88aee2fe4b32d6c7b6437a647e6c3163d40b76a73cf6a5674ef1d890f95b664ee0afa5359a5c4e07985635bbecbac65d747d3d2da7ec2b8221b17b0ca9dc8a1ac1c07ea6a1e60583e2cb00058e77b7b72a298425cd1b941ad4ec65e8afc50303a22c0f99b0509b4c895f40"),
+        ("eff31487c770a893cfb36f912fbfcbff40d5661771ca4b2cb4eafe524333f5c1",
+         "ef904a29bffc4cf9ee82832451c946ac3c8f8058ae97d8d629831a74c6572bd9ebd0df635cd1f208e2038e760c4994984ce73f0d55ea9f22af83ba4734569d4bc95e18350f740c07eef653cbb9f87910d833751825f0ebefa1abe5420bb52be14cf489b37fe1a72f7de2d10be453b2c9d9eb20c7e3f6edc5a60629178d9478df"),
+        ("b23a1d2b4d97b2ef7785562a7e8bac7eed54ed6e97e29aa51bfe3f12ddad1ff9",
+         "80be107d0884f0d881bb460322f0443d38bd222db8bd0b0a5312a6fedb49c1bbd88fd75d8b9a09486c60123dfa1d73c1cc3169761b17476d3c6b7cbbd727acd0e2c942f4dd96ae3da5de368d26b32286e32de7e5a8cb2949f866a0b80c58116b29fa7fabb3ea7d520ee603e0c25bcaf0b9a5e92ec6a1fe4e0391d1cdbce8c68a"),
+        ("4623227bcc01293b8c130bf771da8c298dede7383243dc0993d2d94823958c4c",
+         "546aff5444b5b79aa6148bd81728704c32decb73a3ba76e9e75885cad9def1d06d6792f8a7d12794e90efed817d96920d728896a4510864370c207f99bd4a608ea121700ef01ed879745ee3e4ceef777eda6d9e5e38b90c86ea6fb0b36504ba4a45d22e86f6db5dd43d98a294bebb9125d5b794e9d2a81181066eb954966a487"),
+    ];
+    let hex = |bytes: Vec<u8>| bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>();
+    for (msg, (short, long)) in h2c_msgs.iter().zip(xmd_vectors) {
+        for (len, expected) in [(0x20, short), (0x80, long)] {
+            let out = expand_message_xmd(msg.as_bytes(), xmd_dst, len).expect("len <= 255 * 32");
+            assert_eq!(hex(out), expected, "expand_message_xmd({:?}, {:#x}) discrepa de RFC 9380", msg, len);
+        }
+    }
+
+    // secp256k1_XMD:SHA-256_SSWU_RO_ y _NU_ (RFC 9380, J.8.1 y J.8.2): P = (x, y)
+    let ro_vectors = [
+        ("c1cae290e291aee617ebaef1be6d73861479c48b841eaba9b7b5852ddfeb1346",
+         "64fa678e07ae116126f08b022a94af6de15985c996c3a91b64c406a960e51067"),
+        ("3377e01eab42db296b512293120c6cee72b6ecf9f9205760bd9ff11fb3cb2c4b",
+         "7f95890f33efebd1044d382a01b1bee0900fb6116f94688d487c6c7b9c8371f6"),
+        ("bac54083f293f1fe08e4a70137260aa90783a5cb84d3f35848b324d0674b0e3a",
+         "4436476085d4c3c4508b60fcf4389c40176adce756b398bdee27bca19758d828"),
+        ("e2167bc785333a37aa562f021f1e881defb853839babf52a7f72b102e41890e9",
+         "f2401dd95cc35867ffed4f367cd564763719fbc6a53e969fb8496a1e6685d873"),
+        ("e3c8d35aaaf0b9b647e88a0a0a7ee5d5bed5ad38238152e4e6fd8c1f8cb7c998",
+         "8446eeb6181bf12f56a9d24e262221cc2f0c4725c7e3803024b5888ee5823aa6"),
+    ];
+    let nu_vectors = [
+        ("a4792346075feae77ac3b30026f99c1441b4ecf666ded19b7522cf65c4c55c5b",
+         "62c59e2a6aeed1b23be5883e833912b08ba06be7f57c0e9cdc663f31639ff3a7"),
+        ("3f3b5842033fff837d504bb4ce2a372bfeadbdbd84a1d2b678b6e1d7ee426b9d",
+         "902910d1fef15d8ae2006fc84f2a5a7bda0e0407dc913062c3a493c4f5d876a5"),
+        ("07644fa6281c694709f53bdd21bed94dab995671e4a8cd1904ec4aa50c59bfdf",
+         "c79f8d1dad79b6540426922f7fbc9579c3018dafeffcd4552b1626b506c21e7b"),
+        ("b734f05e9b9709ab631d960fa26d669c4aeaea64ae62004b9d34f483aa9acc33",
+         "03fc8a4a5a78632e2eb4d8460d69ff33c1d72574b79a35e402e801f2d0b1d6ee"),
+        ("17d22b867658977b5002dbe8d0ee70a8cfddec3eec50fb93f36136070fd9fa6c",
+         "e9178ff02f4dab73480f8dd590328aea99856a7b6cc8e5a6cdf289ecc2a51718"),
+    ];
+    let h2c_dst = b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_";
+    let nu_dst = b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_NU_";
+    for (msg, (ro, nu)) in h2c_msgs.iter().zip(ro_vectors.iter().zip(nu_vectors)) {
+        for (pt, (x, y), name) in [
+            (big::hash_to_curve(msg.as_bytes(), h2c_dst), *ro, "hash_to_curve"),
+            (big::encode_to_curve(msg.as_bytes(), nu_dst), nu, "encode_to_curve"),
+        ] {
+            assert_eq!((pt.x.clone(), pt.y.clone()), (big::from_hex(x), big::from_hex(y)),
+                       "{}({:?}) discrepa del vector de RFC 9380", name, msg);
+            assert!(big::is_on_curve(&pt, &curve.b, &curve.p), "{}({:?}) no esta en la curva", name, msg);
+        }
+    }
+    let h_abc = big::hash_to_curve(b"abc", h2c_dst);
+    println!("    hash_to_curve(\"abc\") = {:#x}... ✓", h_abc.x >> 192u32);
+    println!("    RFC 9380: expand_message_xmd, hash_to_curve y encode_to_curve en los 5 mensajes ✓");
+    let h_toy = hash_to_curve_svdw(&toy.curve, toy.cofactor, b"abc", b"D3-SYMMETRY-TOY_XMD:SHA-256_SVDW_RO_");
+    let h_toy_valid = validate_point(&toy, &h_toy).expect("H(abc) esta en <G>");
+    println!("    Curva toy (SvdW, j = 0 sin SSWU): H(\"abc\") = {} ✓", h_toy_valid.point());
+
+    // ════════════════════════════════════════════════
+    // [11] CURVA GENERAL (a != 0, sin endomorfismo GLV)
+    // ════════════════════════════════════════════════
+    println!();
+    println!("[11] CURVA GENERAL y^2 = x^3 + ax + b (j != 0)");
+
+    // Primera curva con a = 3 sobre el mismo F_p con orden primo
+    let (general, n_gen) = (1..p)
+        .map(|b| Curve::new(3, b, p))
+        .filter(|ec| !ec.is_singular())
+        .map(|ec| (ec, ec.count_points()))
+        .find(|&(_, n)| is_prime(n))
+        .expect("ninguna curva con a = 3 tiene orden primo");
+    println!("    Ecuacion: {}", general);
+    println!("    j-invariante: {} (secp256k1 y la curva toy: j = 0)",
+             general.j_invariant().expect("curva no singular"));
+    println!("    |Aut(E)| = {} (frente a 6 en j = 0)", general.automorphism_count());
+    println!("    Orden del grupo: n = {} (primo)", n_gen);
+    assert_eq!(schoof_count(&general), n_gen as u128, "Schoof discrepa de la fuerza bruta");
+    println!("    Schoof (t mod l para l en {:?} + CRT): n = {} ✓", schoof_primes(p), n_gen);
+
+    let g_gen = general.find_generator(n_gen);
+    assert!(general.is_on_curve(&g_gen), "G no esta en la curva general!");
+    assert!(general.scalar_mul(n_gen, &g_gen).infinity, "[n]G != O");
+    println!("    Generador: G = {}, [n]G = O ✓", g_gen);
+
+    let k_gen = secret_k % n_gen;
+    let q_gen = general.scalar_mul(k_gen, &g_gen);
+    let general_sub = Subgroup::new(general, g_gen, n_gen, 1);
+    let q_gen_valid = validate_point(&general_sub, &q_gen).expect("Q esta en <G>");
+    let (found_gen, ops_gen) = bsgs_standard(&general_sub, &q_gen_valid);
+    assert_eq!(found_gen, k_gen, "BSGS en curva general encontro k incorrecto!");
+    let rho_gen = pollard_rho_standard(&general_sub, &q_gen_valid, rng.next_u64());
+    assert_eq!(general.scalar_mul(rho_gen.k, &g_gen), q_gen, "Rho en curva general encontro k incorrecto!");
+    println!("    BSGS: k = {} en {} ops; Pollard ρ: {} iteraciones ✓", found_gen, ops_gen, rho_gen.iterations);
+    let q_sec1 = encode_point(&q_gen, p, true);
+    assert_eq!(decode_point_on(&general, &q_sec1).ok(), Some(q_gen), "ida y vuelta SEC1");
+    println!("    SEC1: Q = {:02x?} -> {} ✓", q_sec1, q_gen);
+    println!("    Sin automorfismos extra no hay reduccion por clases: solo ±1.");
+
+    // Schoof sobre un primo de 63 bits, fuera del alcance de la fuerza bruta
+    let p63: u64 = 9223372036854775783; // 2^63 - 25
+    let big_general = Curve::new(3, 42, p63);
+    let t0 = Instant::now();
+    let n_big = u64::try_from(schoof_count(&big_general)).expect("p < 2^63: #E cabe en u64");
+    let t_schoof = t0.elapsed();
+    let probe = (1..p63)
+        .find_map(|x| {
+            let rhs = mod_add(mod_add(mod_pow(x, 3, p63), mod_mul(3, x, p63), p63), 42, p63);
+            mod_sqrt(rhs, p63).map(|y| Point::new(x, y))
+        })
+        .expect("la curva tiene puntos");
+    assert!(big_general.scalar_mul(n_big, &probe).infinity, "[#E]P != O");
+    println!("    Schoof con p = 2^63 - 25: #E = {} (t = {}) en {:?}, [#E]P = O ✓",
+             n_big, p63 as i128 + 1 - n_big as i128, t_schoof);
+
+    // Schoof con p >= 2^63: p + 1 - t puede pasar de u64, se comprueba [p + 1]P = [t]P
+    let p64: u64 = 18446744073709551557; // 2^64 - 59
+    assert!(is_prime(p64), "2^64 - 59 no es primo");
+    let curve64 = Curve::new(3, 42, p64);
+    let t0 = Instant::now();
+    let n64 = schoof_count(&curve64);
+    let t_schoof64 = t0.elapsed();
+    let t64 = p64 as i128 + 1 - n64 as i128;
+    assert!(t64 * t64 <= 4 * p64 as i128, "|t| > 2 sqrt(p)");
+    let probe64 = (1..p64)
+        .find_map(|x| {
+            let rhs = mod_add(mod_add(mod_pow(x, 3, p64), mod_mul(3, x, p64), p64), 42, p64);
+            mod_sqrt(rhs, p64).map(|y| Point::new(x, y))
+        })
+        .expect("la curva tiene puntos");
+    let p_plus_1 = curve64.point_add(&curve64.scalar_mul(p64, &probe64), &probe64);
+    let t_probe = curve64.scalar_mul(t64.unsigned_abs() as u64, &probe64);
+    let t_probe = if t64 < 0 { curve64.point_neg(&t_probe) } else { t_probe };
+    assert_eq!(p_plus_1, t_probe, "[#E]P != O");
+    println!("    Schoof con p = 2^64 - 59: #E = {} (t = {}) en {:?}, [p + 1]P = [t]P ✓",
+             n64, t64, t_schoof64);
+
+    // Mestre: orden de puntos por BSGS en el intervalo de Hasse y twist cuadratico
+    let t0 = Instant::now();
+    let n_mestre = mestre_count(&big_general, rng.next_u64());
+    let t_mestre = t0.elapsed();
+    assert_eq!(n_mestre, n_big, "Mestre discrepa de Schoof");
+    let ord_probe = point_order(&big_general, &probe);
+    assert_eq!(n_big % ord_probe, 0, "ord(P) no divide #E");
+    println!("    Mestre (BSGS en el intervalo de Hasse + twist): mismo #E en {:?}, ord(P) = {} ✓",
+             t_mestre, ord_probe);
+
+    // Estructura del grupo y subgrupo de orden primo mas grande
+    let structure = big_general.group_structure(n_big);
+    let (g_big, q_big, h_big) = big_general.prime_order_subgroup(n_big).expect("#E correcto");
+    assert!(big_general.scalar_mul(q_big, &g_big).infinity && !g_big.infinity, "[q]G != O");
+    println!("    E(F_p) ≅ {}; subgrupo de orden primo q = {} con cofactor h = {} ✓",
+             structure, q_big, h_big);
+
+    // ════════════════════════════════════════════════
+    // [12] EXPERIMENTO ESTADISTICO (instancias aleatorias)
+    // ════════════════════════════════════════════════
+    println!();
+    println!("[12] EXPERIMENTO ESTADISTICO ({} instancias aleatorias)", TRIALS);
+    println!("    Cada ensayo: generador aleatorio de <G>, k uniforme en [1, n), caminos de Rho nuevos.");
+
+    let stats = run_trials(&toy.curve, order, TRIALS, rng.next_u64()).expect("la curva toy admite GLV");
+    println!("    ┌───────────────────────────┬────────────┬────────────┐");
+    println!("    │ Metodo                    │ Media      │ Desv. tip. │");
+    println!("    ├───────────────────────────┼────────────┼────────────┤");
+    for (name, sample) in [
+        ("BSGS estandar", stats.bsgs_standard),
+        ("BSGS con D=-3", stats.bsgs_glv),
+        ("Pollard ρ estandar", stats.rho_standard),
+        ("Pollard ρ con D=-3", stats.rho_glv),
+    ] {
+        println!("    │ {:<25} │ {:>10.1} │ {:>10.1} │", name, sample.mean, sample.stddev);
+    }
+    println!("    └───────────────────────────┴────────────┴────────────┘");
+    println!("    Speedup medio: BSGS {:.2}x, Pollard ρ {:.2}x (teorico sqrt(6) = {:.2}x)",
+             stats.bsgs_speedup(), stats.rho_speedup(), (6.0f64).sqrt());
+
     println!();
     println!("=== Fin ===");
 }
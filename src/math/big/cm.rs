@@ -0,0 +1,133 @@
+/// Orden de y^2 = x^3 + b por multiplicacion compleja (j = 0, D = -3).
+///
+/// Para p ≡ 1 (mod 3), p se descompone en Z[omega] como p = pi * conj(pi)
+/// con pi = A + B*omega, es decir p = A^2 - AB + B^2. Con pi primario
+/// (pi ≡ 2 mod 3), Ireland-Rosen (cap. 18) da
+///
+///   #E(F_p) = p + 1 + Tr(conj(chi) * pi),   chi = (4b / pi)_6
+///
+/// donde chi es el simbolo de residuo sextico. Las seis elecciones de chi
+/// son las trazas de los seis twists sexticos. Para p ≡ 2 (mod 3) la curva
+/// es supersingular y #E(F_p) = p + 1. Coste: una raiz cuadrada y una
+/// exponenciacion modular, frente al O(p) del conteo exhaustivo.
+
+use num_bigint::{BigInt, BigUint};
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+
+use super::field::*;
+
+/// Elemento A + B*omega de Z[omega], con omega^2 = -1 - omega.
+type Eisenstein = (BigInt, BigInt);
+
+fn eis_mul(x: &Eisenstein, y: &Eisenstein) -> Eisenstein {
+    // (a + b w)(c + d w) = ac - bd + (ad + bc - bd) w
+    let bd = &x.1 * &y.1;
+    (&x.0 * &y.0 - &bd, &x.0 * &y.1 + &x.1 * &y.0 - bd)
+}
+
+fn eis_conj(x: &Eisenstein) -> Eisenstein {
+    // conj(a + b w) = a + b w^2 = (a - b) - b w
+    (&x.0 - &x.1, -&x.1)
+}
+
+/// Tr(a + b w) = 2a - b.
+fn eis_trace(x: &Eisenstein) -> BigInt {
+    BigInt::from(2) * &x.0 - &x.1
+}
+
+/// Las seis unidades de Z[omega] en el orden 1, w, w^2, -1, -w, -w^2.
+fn eis_units() -> [Eisenstein; 6] {
+    let (zero, one) = (BigInt::zero(), BigInt::one());
+    [
+        (one.clone(), zero.clone()),
+        (zero.clone(), one.clone()),
+        (-&one, -&one),
+        (-&one, zero.clone()),
+        (zero.clone(), -&one),
+        (one.clone(), one),
+    ]
+}
+
+/// Cornacchia: (x, y) con p = x^2 + 3*y^2. Requiere p ≡ 1 (mod 3).
+pub fn cornacchia_d3(p: &BigUint) -> Option<(BigUint, BigUint)> {
+    let r = mod_sqrt(&(p - 3u32), p)?;
+    // raiz en (p/2, p) para que el algoritmo de Euclides arranque bien
+    let r = if &r * 2u32 < *p { p - &r } else { r };
+    let bound = p.sqrt();
+    let (mut a, mut b) = (p.clone(), r);
+    while b > bound {
+        let t = &a % &b;
+        a = b;
+        b = t;
+    }
+    let rest = p - &b * &b;
+    if !(&rest % 3u32).is_zero() {
+        return None;
+    }
+    let y2 = rest / 3u32;
+    let y = y2.sqrt();
+    if &y * &y != y2 {
+        return None;
+    }
+    Some((b, y))
+}
+
+/// Primo pi de Z[omega] sobre p, primario (A ≡ 2, B ≡ 0 mod 3).
+pub fn primary_prime(p: &BigUint) -> Option<(BigInt, BigInt)> {
+    let (x, y) = cornacchia_d3(p)?;
+    // x + y*sqrt(-3) = x + y*(1 + 2w)
+    let (x, y) = (BigInt::from(x), BigInt::from(y));
+    let pi = (&x + &y, BigInt::from(2) * y);
+    let three = BigInt::from(3);
+    eis_units().iter().map(|u| eis_mul(u, &pi)).find(|(a, b)| {
+        a.mod_floor(&three) == BigInt::from(2) && b.mod_floor(&three).is_zero()
+    })
+}
+
+fn to_mod(x: &BigInt, p: &BigUint) -> BigUint {
+    x.mod_floor(&BigInt::from(p.clone())).to_biguint().expect("resto no negativo")
+}
+
+/// #E(F_p) para y^2 = x^3 + b por la formula de Ireland-Rosen.
+/// None si p < 5 o b ≡ 0 (mod p) (curva singular).
+pub fn count_points_cm(b: &BigUint, p: &BigUint) -> Option<BigUint> {
+    let b = b % p;
+    if *p < BigUint::from(5u32) || b.is_zero() {
+        return None;
+    }
+    if (p % 3u32) == BigUint::from(2u32) {
+        return Some(p + 1u32);
+    }
+
+    let (a, bb) = primary_prime(p)?;
+    // omega -> w con A + B*w ≡ 0 (mod p): isomorfismo Z[omega]/pi ≅ F_p
+    let w = mod_mul(&to_mod(&-&a, p), &mod_inv(&to_mod(&bb, p), p), p);
+
+    // chi ≡ (4b)^((p-1)/6) (mod pi), una raiz sexta de la unidad
+    let chi = mod_pow(&((b * 4u32) % p), &((p - 1u32) / 6u32), p);
+    let unit = eis_units().into_iter().find(|(u0, u1)| {
+        let image = mod_add(&to_mod(u0, p), &mod_mul(&to_mod(u1, p), &w, p), p);
+        image == chi
+    })?;
+
+    let pi = (a, bb);
+    let t = eis_trace(&eis_mul(&eis_conj(&unit), &pi));
+    let n: BigInt = BigInt::from(p.clone()) + 1 + t;
+    debug_assert!(n.is_positive());
+    n.to_biguint()
+}
+
+/// Los seis ordenes #E_i(F_p) de los twists sexticos de y^2 = x^3 + b
+/// (p ≡ 1 mod 3), es decir p + 1 + Tr(u * pi) para las seis unidades u.
+pub fn sextic_twist_orders(p: &BigUint) -> Option<[BigUint; 6]> {
+    if (p % 3u32) != BigUint::one() {
+        return None;
+    }
+    let pi = primary_prime(p)?;
+    let pp: BigInt = BigInt::from(p.clone()) + 1;
+    let orders = eis_units().map(|u| {
+        (&pp + eis_trace(&eis_mul(&u, &pi))).to_biguint().expect("orden positivo")
+    });
+    Some(orders)
+}
@@ -0,0 +1,163 @@
+/// Operaciones de curva eliptica y^2 = x^3 + b sobre F_p con BigUint.
+///
+/// Espejo de `math::curve` para primos de tamano real (secp256k1).
+
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+use super::field::*;
+use crate::math::wnaf::{wnaf, wnaf_window};
+
+/// Punto afin con coordenadas de precision arbitraria.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BigPoint {
+    pub x: BigUint,
+    pub y: BigUint,
+    pub infinity: bool,
+}
+
+impl BigPoint {
+    pub fn infinity() -> Self {
+        BigPoint { x: BigUint::zero(), y: BigUint::zero(), infinity: true }
+    }
+
+    pub fn new(x: BigUint, y: BigUint) -> Self {
+        BigPoint { x, y, infinity: false }
+    }
+}
+
+impl std::fmt::Display for BigPoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.infinity {
+            write!(f, "O (punto en el infinito)")
+        } else {
+            write!(f, "({:#x}, {:#x})", self.x, self.y)
+        }
+    }
+}
+
+/// Parametros de una curva y^2 = x^3 + b con generador G de orden primo n.
+#[derive(Clone, Debug)]
+pub struct CurveParams {
+    pub p: BigUint,
+    pub b: BigUint,
+    pub n: BigUint,
+    pub g: BigPoint,
+}
+
+/// Parametros de secp256k1 (SEC 2, seccion 2.4.1).
+pub fn secp256k1() -> CurveParams {
+    CurveParams {
+        p: from_hex("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F"),
+        b: BigUint::from(7u32),
+        n: from_hex("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141"),
+        g: BigPoint::new(
+            from_hex("79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798"),
+            from_hex("483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8"),
+        ),
+    }
+}
+
+/// Verifica si P esta en la curva y^2 = x^3 + b (mod p).
+pub fn is_on_curve(pt: &BigPoint, b: &BigUint, p: &BigUint) -> bool {
+    if pt.infinity {
+        return true;
+    }
+    let y2 = mod_mul(&pt.y, &pt.y, p);
+    let x3 = mod_mul(&mod_mul(&pt.x, &pt.x, p), &pt.x, p);
+    let rhs = mod_add(&x3, b, p);
+    y2 == rhs
+}
+
+/// Suma de puntos P + Q en y^2 = x^3 + b (mod p).
+pub fn point_add(p1: &BigPoint, p2: &BigPoint, p: &BigUint) -> BigPoint {
+    if p1.infinity {
+        return p2.clone();
+    }
+    if p2.infinity {
+        return p1.clone();
+    }
+    if p1.x == p2.x {
+        if p1.y == p2.y && !p1.y.is_zero() {
+            return point_double(p1, p);
+        }
+        // P + (-P) = O, o ambos tienen y=0
+        return BigPoint::infinity();
+    }
+
+    let dy = mod_sub(&p2.y, &p1.y, p);
+    let dx = mod_sub(&p2.x, &p1.x, p);
+    let slope = mod_mul(&dy, &mod_inv(&dx, p), p);
+
+    let x3 = mod_sub(&mod_sub(&mod_mul(&slope, &slope, p), &p1.x, p), &p2.x, p);
+    let y3 = mod_sub(&mod_mul(&slope, &mod_sub(&p1.x, &x3, p), p), &p1.y, p);
+
+    BigPoint::new(x3, y3)
+}
+
+/// Duplicacion de punto 2P en y^2 = x^3 + b (mod p), con a = 0.
+pub fn point_double(pt: &BigPoint, p: &BigUint) -> BigPoint {
+    if pt.infinity || pt.y.is_zero() {
+        return BigPoint::infinity();
+    }
+
+    // slope = 3x^2 / (2y)
+    let x2 = mod_mul(&pt.x, &pt.x, p);
+    let num = mod_mul(&BigUint::from(3u32), &x2, p);
+    let den = mod_add(&pt.y, &pt.y, p);
+    let slope = mod_mul(&num, &mod_inv(&den, p), p);
+
+    let x3 = mod_sub(&mod_mul(&slope, &slope, p), &mod_add(&pt.x, &pt.x, p), p);
+    let y3 = mod_sub(&mod_mul(&slope, &mod_sub(&pt.x, &x3, p), p), &pt.y, p);
+
+    BigPoint::new(x3, y3)
+}
+
+/// Multiplicacion escalar k*P por wNAF: tabla P, 3P, ..., (2^(w-1) - 1)P y
+/// una suma por digito no nulo, con la ventana segun el tamano de k.
+pub fn scalar_mul(k: &BigUint, pt: &BigPoint, p: &BigUint) -> BigPoint {
+    if k.is_zero() || pt.infinity {
+        return BigPoint::infinity();
+    }
+    let w = wnaf_window(k.bits() as u32);
+    let two_p = point_double(pt, p);
+    let mut table = vec![pt.clone()];
+    for i in 1..1usize << (w - 2) {
+        table.push(point_add(&table[i - 1], &two_p, p));
+    }
+
+    let mut result = BigPoint::infinity();
+    for &d in wnaf(&k.to_u64_digits(), w).iter().rev() {
+        result = point_double(&result, p);
+        if d > 0 {
+            result = point_add(&result, &table[(d / 2) as usize], p);
+        } else if d < 0 {
+            result = point_add(&result, &point_neg(&table[(-d / 2) as usize], p), p);
+        }
+    }
+    result
+}
+
+/// k*P por double-and-add binario (del bit mas alto al mas bajo); referencia
+/// para comparar con `scalar_mul`.
+pub fn scalar_mul_binary(k: &BigUint, pt: &BigPoint, p: &BigUint) -> BigPoint {
+    if k.is_zero() || pt.infinity {
+        return BigPoint::infinity();
+    }
+    let mut result = BigPoint::infinity();
+    for i in (0..k.bits()).rev() {
+        result = point_double(&result, p);
+        if k.bit(i) {
+            result = point_add(&result, pt, p);
+        }
+    }
+    result
+}
+
+/// Negacion de un punto: -P = (x, p-y).
+pub fn point_neg(pt: &BigPoint, p: &BigUint) -> BigPoint {
+    if pt.infinity {
+        return BigPoint::infinity();
+    }
+    BigPoint::new(pt.x.clone(), mod_sub(&BigUint::zero(), &pt.y, p))
+}
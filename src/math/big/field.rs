@@ -0,0 +1,110 @@
+/// Aritmetica modular sobre F_p con precision arbitraria (num-bigint).
+///
+/// Misma interfaz que `math::field`, pero con `BigUint` para poder trabajar
+/// con los parametros reales de 256 bits de secp256k1.
+
+use num_bigint::{BigInt, BigUint};
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+
+pub fn mod_add(a: &BigUint, b: &BigUint, p: &BigUint) -> BigUint {
+    (a + b) % p
+}
+
+pub fn mod_sub(a: &BigUint, b: &BigUint, p: &BigUint) -> BigUint {
+    (a % p + p - b % p) % p
+}
+
+pub fn mod_mul(a: &BigUint, b: &BigUint, p: &BigUint) -> BigUint {
+    (a * b) % p
+}
+
+pub fn mod_pow(base: &BigUint, exp: &BigUint, p: &BigUint) -> BigUint {
+    if p.is_one() {
+        return BigUint::zero();
+    }
+    base.modpow(exp, p)
+}
+
+/// Inverso modular via extended GCD. Panics si a == 0.
+pub fn mod_inv(a: &BigUint, p: &BigUint) -> BigUint {
+    let a = BigInt::from(a % p);
+    let m = BigInt::from(p.clone());
+    assert!(!a.is_zero(), "0 no tiene inverso modular");
+
+    let egcd = a.extended_gcd(&m);
+    let mut s = egcd.x % &m;
+    if s.is_negative() {
+        s += &m;
+    }
+    s.to_biguint().expect("s reducido es no negativo")
+}
+
+/// Raiz cuadrada modular via Tonelli-Shanks. Retorna None si `a` no es QR.
+pub fn mod_sqrt(a: &BigUint, p: &BigUint) -> Option<BigUint> {
+    let a = a % p;
+    if a.is_zero() {
+        return Some(a);
+    }
+    let one = BigUint::one();
+    let p_minus_1 = p - &one;
+
+    // Chequear si a es residuo cuadratico
+    if mod_pow(&a, &(&p_minus_1 >> 1), p) != one {
+        return None;
+    }
+
+    // Caso p ≡ 3 (mod 4), que incluye a secp256k1
+    if p.bit(0) && p.bit(1) {
+        return Some(mod_pow(&a, &((p + &one) >> 2), p));
+    }
+
+    // Tonelli-Shanks general
+    // Escribir p-1 = Q * 2^S
+    let s = p_minus_1.trailing_zeros().expect("p > 1");
+    let q = &p_minus_1 >> s;
+
+    // Encontrar un no-residuo cuadratico z
+    let mut z = BigUint::from(2u32);
+    while mod_pow(&z, &(&p_minus_1 >> 1), p) != p_minus_1 {
+        z += 1u32;
+    }
+
+    let mut m = s;
+    let mut c = mod_pow(&z, &q, p);
+    let mut t = mod_pow(&a, &q, p);
+    let mut r = mod_pow(&a, &((&q + &one) >> 1), p);
+
+    loop {
+        if t.is_one() {
+            return Some(r);
+        }
+        // Encontrar el menor i tal que t^(2^i) = 1
+        let mut i = 1u64;
+        let mut tmp = mod_mul(&t, &t, p);
+        while !tmp.is_one() {
+            tmp = mod_mul(&tmp, &tmp, p);
+            i += 1;
+        }
+        let b = mod_pow(&c, &(BigUint::one() << (m - i - 1)), p);
+        m = i;
+        c = mod_mul(&b, &b, p);
+        t = mod_mul(&t, &c, p);
+        r = mod_mul(&r, &b, p);
+    }
+}
+
+/// Simbolo de Legendre: retorna 1 si QR, p-1 si NQR, 0 si a==0.
+pub fn legendre(a: &BigUint, p: &BigUint) -> BigUint {
+    mod_pow(&(a % p), &((p - 1u32) >> 1), p)
+}
+
+/// Parsea un entero en hexadecimal (sin prefijo `0x`).
+pub fn from_hex(s: &str) -> BigUint {
+    BigUint::parse_bytes(s.as_bytes(), 16).expect("hex invalido")
+}
+
+/// Parsea un entero en decimal.
+pub fn from_dec(s: &str) -> BigUint {
+    BigUint::parse_bytes(s.as_bytes(), 10).expect("decimal invalido")
+}
@@ -0,0 +1,138 @@
+/// Hash a curva para secp256k1: suite secp256k1_XMD:SHA-256_SSWU_RO_ (y la
+/// NU_ de encode_to_curve) de RFC 9380, seccion 8.7.
+///
+/// Simplified SWU necesita A != 0, y secp256k1 tiene A = 0; por eso el mapa
+/// se aplica en la curva 3-isogena E': y^2 = x^3 + A'x + B' y el punto se
+/// lleva a secp256k1 con la isogenia de grado 3 (apendice E.1). El cofactor
+/// es 1, asi que no hay que limpiarlo. `expand_message_xmd` es el del
+/// backend u64 (`math::h2c`).
+
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+use super::curve::*;
+use super::field::*;
+use crate::math::h2c::{expand_message_xmd, h2c_field_len};
+
+/// A' de la curva 3-isogena E'.
+const ISO_A: &str = "3f8731abdd661adca08a5558f0f5d272e953d363cb6f0e5d405447c01a444533";
+/// B' de la curva 3-isogena E'.
+const ISO_B: u32 = 1771;
+/// Z de SSWU para E' (Z = -11).
+const SSWU_Z_NEG: u32 = 11;
+
+/// Coeficientes de la isogenia E' -> secp256k1, de grado ascendente:
+/// x = x_num(x') / x_den(x'),  y = y' * y_num(x') / y_den(x').
+const ISO_X_NUM: [&str; 4] = [
+    "8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa8c7",
+    "07d3d4c80bc321d5b9f315cea7fd44c5d595d2fc0bf63b92dfff1044f17c6581",
+    "534c328d23f234e6e2a413deca25caece4506144037c40314ecbd0b53d9dd262",
+    "8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa88c",
+];
+const ISO_X_DEN: [&str; 3] = [
+    "d35771193d94918a9ca34ccbb7b640dd86cd409542f8487d9fe6b745781eb49b",
+    "edadc6f64383dc1df7c4b2d51b54225406d36b641f5e41bbc52a56612a8c6d14",
+    "1",
+];
+const ISO_Y_NUM: [&str; 4] = [
+    "4bda12f684bda12f684bda12f684bda12f684bda12f684bda12f684b8e38e23c",
+    "c75e0c32d5cb7c0fa9d0a54b12a0a6d5647ab046d686da6fdffc90fc201d71a3",
+    "29a6194691f91a73715209ef6512e576722830a201be2018a765e85a9ecee931",
+    "2f684bda12f684bda12f684bda12f684bda12f684bda12f684bda12f38e38d84",
+];
+const ISO_Y_DEN: [&str; 4] = [
+    "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffff93b",
+    "7a06534bb8bdb49fd5e9e6632722c2989467c1bfc8e8d978dfb425d2685c2573",
+    "6484aa716545ca2cf3a70c3fa8fe337e0a3d21162f0d6299a7bf8192bfd2a76f",
+    "1",
+];
+
+/// `count` elementos de F_p a partir de (msg, DST) (seccion 5.2).
+pub fn hash_to_field(msg: &[u8], dst: &[u8], count: usize, p: &BigUint) -> Vec<BigUint> {
+    let len = h2c_field_len(p.bits() as usize);
+    let bytes = expand_message_xmd(msg, dst, count * len).expect("count * L <= 8160");
+    bytes.chunks(len).map(|chunk| BigUint::from_bytes_be(chunk) % p).collect()
+}
+
+fn is_square(x: &BigUint, p: &BigUint) -> bool {
+    x.is_zero() || legendre(x, p).is_one()
+}
+
+fn inv0(x: &BigUint, p: &BigUint) -> BigUint {
+    if x.is_zero() { BigUint::zero() } else { mod_inv(x, p) }
+}
+
+/// sgn0 para F_p: paridad del representante canonico.
+fn sgn0(x: &BigUint) -> bool {
+    x.bit(0)
+}
+
+/// Evalua un polinomio (coeficientes en hex, grado ascendente) por Horner.
+fn eval_poly(coeffs: &[&str], x: &BigUint, p: &BigUint) -> BigUint {
+    coeffs.iter().rev().fold(BigUint::zero(), |acc, c| mod_add(&mod_mul(&acc, x, p), &from_hex(c), p))
+}
+
+/// Simplified SWU sobre E' (seccion 6.6.2, version directa): u -> (x', y').
+pub fn map_to_curve_sswu(u: &BigUint, p: &BigUint) -> BigPoint {
+    let a = from_hex(ISO_A);
+    let b = BigUint::from(ISO_B);
+    let z = p - SSWU_Z_NEG;
+    let g = |x: &BigUint| mod_add(&mod_mul(&mod_add(&mod_mul(x, x, p), &a, p), x, p), &b, p);
+
+    // x1 = (-B/A) (1 + 1/(Z^2 u^4 + Z u^2)), o B/(Z A) si el denominador es 0
+    let zu2 = mod_mul(&z, &mod_mul(u, u, p), p);
+    let tv1 = inv0(&mod_add(&mod_mul(&zu2, &zu2, p), &zu2, p), p);
+    let x1 = if tv1.is_zero() {
+        mod_mul(&b, &mod_inv(&mod_mul(&z, &a, p), p), p)
+    } else {
+        let minus_b_over_a = mod_sub(&BigUint::zero(), &mod_mul(&b, &mod_inv(&a, p), p), p);
+        mod_mul(&minus_b_over_a, &mod_add(&BigUint::one(), &tv1, p), p)
+    };
+    let gx1 = g(&x1);
+    let (x, gx) = if is_square(&gx1, p) {
+        (x1, gx1)
+    } else {
+        let x2 = mod_mul(&zu2, &x1, p);
+        let gx2 = g(&x2);
+        (x2, gx2)
+    };
+    let y = mod_sqrt(&gx, p).expect("g(x1) o g(x2) es cuadrado");
+    let y = if sgn0(u) == sgn0(&y) { y } else { mod_sub(&BigUint::zero(), &y, p) };
+    BigPoint::new(x, y)
+}
+
+/// Isogenia de grado 3 E' -> secp256k1 (apendice E.1).
+pub fn iso_map(pt: &BigPoint, p: &BigUint) -> BigPoint {
+    if pt.infinity {
+        return BigPoint::infinity();
+    }
+    let x_den = eval_poly(&ISO_X_DEN, &pt.x, p);
+    let y_den = eval_poly(&ISO_Y_DEN, &pt.x, p);
+    if x_den.is_zero() || y_den.is_zero() {
+        // x' es la abscisa de un punto del nucleo
+        return BigPoint::infinity();
+    }
+    let x = mod_mul(&eval_poly(&ISO_X_NUM, &pt.x, p), &mod_inv(&x_den, p), p);
+    let y_num = mod_mul(&pt.y, &eval_poly(&ISO_Y_NUM, &pt.x, p), p);
+    let y = mod_mul(&y_num, &mod_inv(&y_den, p), p);
+    BigPoint::new(x, y)
+}
+
+/// u -> punto de secp256k1: SSWU en E' seguido de la isogenia.
+pub fn map_to_curve(u: &BigUint, p: &BigUint) -> BigPoint {
+    iso_map(&map_to_curve_sswu(u, p), p)
+}
+
+/// hash_to_curve (suite _RO_): map(u0) + map(u1), con u0, u1 de hash_to_field.
+pub fn hash_to_curve(msg: &[u8], dst: &[u8]) -> BigPoint {
+    let p = secp256k1().p;
+    let u = hash_to_field(msg, dst, 2, &p);
+    point_add(&map_to_curve(&u[0], &p), &map_to_curve(&u[1], &p), &p)
+}
+
+/// encode_to_curve (suite _NU_): un solo u, distribucion no uniforme.
+pub fn encode_to_curve(msg: &[u8], dst: &[u8]) -> BigPoint {
+    let p = secp256k1().p;
+    let u = hash_to_field(msg, dst, 1, &p);
+    map_to_curve(&u[0], &p)
+}
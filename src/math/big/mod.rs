@@ -0,0 +1,16 @@
+/// Backend de precision arbitraria (BigUint) para parametros criptograficos reales.
+///
+/// No se re-exporta desde `math` para no colisionar con los nombres del
+/// backend u64: usar `d3_symmetry::math::big::*`.
+
+pub mod field;
+pub mod curve;
+pub mod cm;
+pub mod sec1;
+pub mod h2c;
+
+pub use field::*;
+pub use curve::*;
+pub use cm::*;
+pub use sec1::*;
+pub use h2c::*;
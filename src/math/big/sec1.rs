@@ -0,0 +1,85 @@
+/// Codificacion SEC1 de `BigPoint` (mismo formato que `math::sec1`).
+///
+/// Con los parametros de secp256k1 produce las claves publicas estandar de
+/// 33 bytes (comprimidas) y 65 bytes (sin comprimir).
+
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+use crate::error::{Error, Result};
+use crate::math::sec1::{SEC1_EVEN, SEC1_INFINITY, SEC1_ODD, SEC1_UNCOMPRESSED};
+use super::curve::*;
+use super::field::*;
+
+/// Bytes de una coordenada de F_p: ceil(log2(p) / 8).
+pub fn field_len(p: &BigUint) -> usize {
+    (p.bits() as usize).div_ceil(8)
+}
+
+fn write_coord(out: &mut Vec<u8>, v: &BigUint, len: usize) {
+    let bytes = v.to_bytes_be();
+    out.resize(out.len() + len - bytes.len(), 0);
+    out.extend_from_slice(&bytes);
+}
+
+fn read_coord(bytes: &[u8], p: &BigUint) -> Result<BigUint> {
+    let v = BigUint::from_bytes_be(bytes);
+    if &v >= p {
+        return Err(Error::InvalidEncoding { reason: "coordenada >= p" });
+    }
+    Ok(v)
+}
+
+/// Codifica P en SEC1, comprimido o no.
+pub fn encode_point(pt: &BigPoint, p: &BigUint, compressed: bool) -> Vec<u8> {
+    if pt.infinity {
+        return vec![SEC1_INFINITY];
+    }
+    let len = field_len(p);
+    let mut out = Vec::with_capacity(1 + 2 * len);
+    if compressed {
+        out.push(if pt.y.bit(0) { SEC1_ODD } else { SEC1_EVEN });
+        write_coord(&mut out, &pt.x, len);
+    } else {
+        out.push(SEC1_UNCOMPRESSED);
+        write_coord(&mut out, &pt.x, len);
+        write_coord(&mut out, &pt.y, len);
+    }
+    out
+}
+
+/// Decodifica un punto SEC1 de y^2 = x^3 + b, comprobando que este en la curva.
+/// Mismos errores que `math::sec1::decode_point_on`.
+pub fn decode_point(bytes: &[u8], b: &BigUint, p: &BigUint) -> Result<BigPoint> {
+    let len = field_len(p);
+    let (&prefix, body) = bytes
+        .split_first()
+        .ok_or(Error::InvalidEncoding { reason: "entrada vacia" })?;
+
+    match prefix {
+        SEC1_INFINITY if body.is_empty() => Ok(BigPoint::infinity()),
+        SEC1_EVEN | SEC1_ODD if body.len() == len => {
+            let x = read_coord(body, p)?;
+            let rhs = mod_add(&mod_mul(&mod_mul(&x, &x, p), &x, p), b, p);
+            let y = mod_sqrt(&rhs, p).ok_or(Error::PointNotOnCurve)?;
+            let odd = prefix == SEC1_ODD;
+            if y.is_zero() && odd {
+                // y = 0 solo admite el prefijo par
+                return Err(Error::PointNotOnCurve);
+            }
+            let y = if y.bit(0) == odd { y } else { p - y };
+            Ok(BigPoint::new(x, y))
+        }
+        SEC1_UNCOMPRESSED if body.len() == 2 * len => {
+            let pt = BigPoint::new(read_coord(&body[..len], p)?, read_coord(&body[len..], p)?);
+            if !is_on_curve(&pt, b, p) {
+                return Err(Error::PointNotOnCurve);
+            }
+            Ok(pt)
+        }
+        SEC1_INFINITY | SEC1_EVEN | SEC1_ODD | SEC1_UNCOMPRESSED => {
+            Err(Error::InvalidEncoding { reason: "longitud incorrecta" })
+        }
+        _ => Err(Error::InvalidEncoding { reason: "prefijo desconocido" }),
+    }
+}
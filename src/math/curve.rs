@@ -1,6 +1,14 @@
 /// Operaciones de curva eliptica y^2 = x^3 + b sobre F_p.
 
+use num_bigint::{BigInt, BigUint};
+
+use crate::error::{Error, Result};
 use super::field::*;
+use super::fp::*;
+use super::wnaf::*;
+use super::msm::*;
+use super::roots::*;
+use super::weierstrass::Curve;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Point {
@@ -17,6 +25,48 @@ impl Point {
     pub fn new(x: u64, y: u64) -> Self {
         Point { x, y, infinity: false }
     }
+
+    /// Coordenadas tipadas en F_p; None representa el punto en el infinito.
+    pub fn to_fp(&self, p: u64) -> Option<(Fp, Fp)> {
+        if self.infinity {
+            None
+        } else {
+            Some((Fp::new(self.x, p), Fp::new(self.y, p)))
+        }
+    }
+
+    pub fn from_fp(pt: Option<(Fp, Fp)>) -> Self {
+        match pt {
+            Some((x, y)) => Point::new(x.value(), y.value()),
+            None => Point::infinity(),
+        }
+    }
+
+    /// Coordenadas en forma de Montgomery segun `ctx`.
+    pub fn to_mont(&self, ctx: &MontgomeryCtx) -> Option<(MontFp, MontFp)> {
+        if self.infinity {
+            None
+        } else {
+            Some((MontFp::new(self.x, ctx), MontFp::new(self.y, ctx)))
+        }
+    }
+
+    pub fn from_mont(pt: Option<(MontFp, MontFp)>) -> Self {
+        match pt {
+            Some((x, y)) => Point::new(x.value(), y.value()),
+            None => Point::infinity(),
+        }
+    }
+
+    /// Coordenadas en cualquier cuerpo que contenga a F_p (p.ej. F_{p^k}),
+    /// embebidas con `like.lift`.
+    pub fn to_field<F: Field>(&self, like: &F) -> Option<(F, F)> {
+        if self.infinity {
+            None
+        } else {
+            Some((like.lift(self.x), like.lift(self.y)))
+        }
+    }
 }
 
 impl std::fmt::Display for Point {
@@ -31,144 +81,305 @@ impl std::fmt::Display for Point {
 
 /// Verifica si P esta en la curva y^2 = x^3 + b (mod p).
 pub fn is_on_curve(p_point: &Point, b: u64, p: u64) -> bool {
-    if p_point.infinity {
-        return true;
-    }
-    let y2 = mod_mul(p_point.y, p_point.y, p);
-    let x3 = mod_pow(p_point.x, 3, p);
-    let rhs = mod_add(x3, b, p);
-    y2 == rhs
+    Curve::new(0, b, p).is_on_curve(p_point)
 }
 
-/// Suma de puntos P + Q en y^2 = x^3 + b (mod p).
-pub fn point_add(p1: &Point, p2: &Point, p: u64) -> Point {
-    if p1.infinity {
-        return *p2;
-    }
-    if p2.infinity {
-        return *p1;
+/// Verifica y^2 = x^3 + b para un punto con coordenadas en el cuerpo F.
+pub fn is_on_curve_over<F: Field>(pt: &Option<(F, F)>, b: u64) -> bool {
+    match pt {
+        None => true,
+        Some((x, y)) => y.square() == x.square() * *x + x.lift(b),
     }
-    if p1.x == p2.x {
-        if p1.y == p2.y && p1.y != 0 {
-            return point_double(p1, p);
+}
+
+/// Punto (x, y) de y^2 = x^3 + b con la abscisa dada, si x^3 + b es cuadrado en F.
+pub fn lift_x<F: Field>(x: F, b: u64) -> Option<(F, F)> {
+    let y = (x.square() * x + x.lift(b)).sqrt()?;
+    Some((x, y))
+}
+
+/// Suma afin P + Q en y^2 = x^3 + b, generica sobre el cuerpo F.
+/// `None` representa el punto en el infinito.
+pub fn affine_add<F: Field>(p1: Option<(F, F)>, p2: Option<(F, F)>) -> Option<(F, F)> {
+    let zero = p1.or(p2)?.0.zero();
+    affine_add_a(p1, p2, zero)
+}
+
+/// Suma afin P + Q en y^2 = x^3 + ax + b. `a` solo interviene si P == Q.
+pub fn affine_add_a<F: Field>(p1: Option<(F, F)>, p2: Option<(F, F)>, a: F) -> Option<(F, F)> {
+    let (x1, y1) = match p1 {
+        Some(c) => c,
+        None => return p2,
+    };
+    let (x2, y2) = match p2 {
+        Some(c) => c,
+        None => return p1,
+    };
+    if x1 == x2 {
+        if y1 == y2 && !y1.is_zero() {
+            return affine_double_a(p1, a);
         }
         // P + (-P) = O, o ambos tienen y=0
-        return Point::infinity();
+        return None;
     }
 
-    let dy = mod_sub(p2.y, p1.y, p);
-    let dx = mod_sub(p2.x, p1.x, p);
-    let slope = mod_mul(dy, mod_inv(dx, p), p);
+    let slope = (y2 - y1) * (x2 - x1).inv()?;
+    let x3 = slope.square() - x1 - x2;
+    let y3 = slope * (x1 - x3) - y1;
+    Some((x3, y3))
+}
+
+/// Duplicacion afin 2P en y^2 = x^3 + b (a = 0), generica sobre el cuerpo F.
+pub fn affine_double<F: Field>(pt: Option<(F, F)>) -> Option<(F, F)> {
+    let (x, _) = pt?;
+    affine_double_a(pt, x.zero())
+}
+
+/// Duplicacion afin 2P en y^2 = x^3 + ax + b.
+pub fn affine_double_a<F: Field>(pt: Option<(F, F)>, a: F) -> Option<(F, F)> {
+    let (x, y) = pt?;
+    if y.is_zero() {
+        return None;
+    }
+
+    // slope = (3x^2 + a) / (2y)
+    let slope = (x.square() * x.lift(3) + a) * y.double().inv()?;
+    let x3 = slope.square() - x.double();
+    let y3 = slope * (x - x3) - y;
+    Some((x3, y3))
+}
+
+/// Multiplicacion escalar generica k*P (double-and-add desde el bit mas alto).
+/// `k` va en limbs u64 little-endian para admitir escalares de mas de 64 bits.
+pub fn affine_mul<F: Field>(k: &[u64], pt: Option<(F, F)>) -> Option<(F, F)> {
+    let (x, _) = pt?;
+    affine_mul_a(k, pt, x.zero())
+}
 
-    let x3 = mod_sub(mod_sub(mod_mul(slope, slope, p), p1.x, p), p2.x, p);
-    let y3 = mod_sub(mod_mul(slope, mod_sub(p1.x, x3, p), p), p1.y, p);
+/// Como `affine_mul` en y^2 = x^3 + ax + b.
+pub fn affine_mul_a<F: Field>(k: &[u64], pt: Option<(F, F)>, a: F) -> Option<(F, F)> {
+    let mut result = None;
+    for i in (0..k.len() * 64).rev() {
+        result = affine_double_a(result, a);
+        if (k[i / 64] >> (i % 64)) & 1 == 1 {
+            result = affine_add_a(result, pt, a);
+        }
+    }
+    result
+}
 
-    Point::new(x3, y3)
+/// Suma de puntos P + Q en y^2 = x^3 + b (mod p).
+pub fn point_add(p1: &Point, p2: &Point, p: u64) -> Point {
+    Point::from_fp(affine_add(p1.to_fp(p), p2.to_fp(p)))
+}
+
+/// Suma P + Q comprobando antes que ambos esten en y^2 = x^3 + b.
+pub fn try_point_add(p1: &Point, p2: &Point, b: u64, p: u64) -> Result<Point> {
+    if !is_on_curve(p1, b, p) || !is_on_curve(p2, b, p) {
+        return Err(Error::PointNotOnCurve);
+    }
+    Ok(point_add(p1, p2, p))
 }
 
 /// Duplicacion de punto 2P en y^2 = x^3 + b (mod p).
 /// Para y^2 = x^3 + b, el coeficiente a = 0.
 pub fn point_double(pt: &Point, p: u64) -> Point {
-    if pt.infinity || pt.y == 0 {
-        return Point::infinity();
-    }
+    Point::from_fp(affine_double(pt.to_fp(p)))
+}
 
-    // slope = 3x^2 / (2y)  (a=0 para nuestra curva)
-    let x2 = mod_mul(pt.x, pt.x, p);
-    let num = mod_mul(3, x2, p);
-    let den = mod_mul(2, pt.y, p);
-    let slope = mod_mul(num, mod_inv(den, p), p);
+/// Multiplicacion escalar k*P por wNAF en coordenadas jacobianas, con la
+/// ventana segun el tamano de k (`wnaf_window`).
+pub fn scalar_mul(k: u64, pt: &Point, p: u64) -> Point {
+    Point::from_fp(wnaf_mul(&[k], pt.to_fp(p), window_u64(k)))
+}
 
-    let x3 = mod_sub(mod_mul(slope, slope, p), mod_mul(2, pt.x, p), p);
-    let y3 = mod_sub(mod_mul(slope, mod_sub(pt.x, x3, p), p), pt.y, p);
+fn window_u64(k: u64) -> u32 {
+    wnaf_window(64 - k.leading_zeros())
+}
 
-    Point::new(x3, y3)
+/// P + Q en y^2 = x^3 + ax + b (mod p).
+pub fn point_add_a(p1: &Point, p2: &Point, a: u64, p: u64) -> Point {
+    Point::from_fp(affine_add_a(p1.to_fp(p), p2.to_fp(p), Fp::new(a, p)))
 }
 
-/// Multiplicacion escalar k*P usando double-and-add.
-pub fn scalar_mul(k: u64, pt: &Point, p: u64) -> Point {
-    if k == 0 || pt.infinity {
-        return Point::infinity();
-    }
-    let mut result = Point::infinity();
-    let mut base = *pt;
-    let mut k = k;
+/// 2P en y^2 = x^3 + ax + b (mod p).
+pub fn point_double_a(pt: &Point, a: u64, p: u64) -> Point {
+    Point::from_fp(affine_double_a(pt.to_fp(p), Fp::new(a, p)))
+}
 
-    while k > 0 {
-        if k & 1 == 1 {
-            result = point_add(&result, &base, p);
-        }
-        base = point_double(&base, p);
-        k >>= 1;
-    }
-    result
+/// k*P en y^2 = x^3 + ax + b (mod p), por wNAF.
+pub fn scalar_mul_a(k: u64, pt: &Point, a: u64, p: u64) -> Point {
+    Point::from_fp(wnaf_mul_a(&[k], pt.to_fp(p), window_u64(k), Fp::new(a, p)))
+}
+
+/// P + Q usando el contexto de Montgomery de la curva (mismo resultado que `point_add`).
+pub fn point_add_ctx(p1: &Point, p2: &Point, ctx: &MontgomeryCtx) -> Point {
+    Point::from_mont(affine_add(p1.to_mont(ctx), p2.to_mont(ctx)))
+}
+
+/// 2P usando el contexto de Montgomery de la curva (mismo resultado que `point_double`).
+pub fn point_double_ctx(pt: &Point, ctx: &MontgomeryCtx) -> Point {
+    Point::from_mont(affine_double(pt.to_mont(ctx)))
+}
+
+/// k*P por wNAF con toda la cadena en forma de Montgomery
+/// (coordenadas jacobianas, una inversion al final).
+pub fn scalar_mul_ctx(k: u64, pt: &Point, ctx: &MontgomeryCtx) -> Point {
+    Point::from_mont(wnaf_mul(&[k], pt.to_mont(ctx), window_u64(k)))
+}
+
+/// sum_i k_i * P_i (Straus o Pippenger segun el tamano del lote).
+pub fn multi_scalar_mul(ks: &[u64], pts: &[Point], p: u64) -> Point {
+    let scalars: Vec<[u64; 1]> = ks.iter().map(|&k| [k]).collect();
+    let points: Vec<_> = pts.iter().map(|pt| pt.to_fp(p)).collect();
+    Point::from_fp(msm(&scalars, &points))
+}
+
+/// Como `point_add_ctx` en y^2 = x^3 + ax + b.
+pub fn point_add_ctx_a(p1: &Point, p2: &Point, a: u64, ctx: &MontgomeryCtx) -> Point {
+    Point::from_mont(affine_add_a(p1.to_mont(ctx), p2.to_mont(ctx), MontFp::new(a, ctx)))
+}
+
+/// Como `point_double_ctx` en y^2 = x^3 + ax + b.
+pub fn point_double_ctx_a(pt: &Point, a: u64, ctx: &MontgomeryCtx) -> Point {
+    Point::from_mont(affine_double_a(pt.to_mont(ctx), MontFp::new(a, ctx)))
+}
+
+/// Como `scalar_mul_ctx` en y^2 = x^3 + ax + b.
+pub fn scalar_mul_ctx_a(k: u64, pt: &Point, a: u64, ctx: &MontgomeryCtx) -> Point {
+    Point::from_mont(wnaf_mul_a(&[k], pt.to_mont(ctx), window_u64(k), MontFp::new(a, ctx)))
+}
+
+/// Suma por lotes P_i + Q_i con una unica inversion compartida (`batch_inv`).
+///
+/// Cada par sigue las mismas reglas que `point_add` (infinito, P + (-P),
+/// duplicacion si P = Q). Es la pieza base para avanzar muchos caminos
+/// de rho o baby steps a la vez.
+pub fn batch_point_add(p1s: &[Point], p2s: &[Point], p: u64) -> Vec<Point> {
+    assert_eq!(p1s.len(), p2s.len(), "los lotes deben tener la misma longitud");
+
+    // Denominador de la pendiente de cada par: x2 - x1 (suma) o 2y (duplicacion).
+    // 0 marca los pares sin pendiente (algun infinito o P + (-P)).
+    let mut dens: Vec<u64> = p1s
+        .iter()
+        .zip(p2s)
+        .map(|(a, b)| {
+            if a.infinity || b.infinity {
+                0
+            } else if a.x != b.x {
+                mod_sub(b.x, a.x, p)
+            } else if a.y == b.y {
+                mod_add(a.y, a.y, p)
+            } else {
+                0
+            }
+        })
+        .collect();
+    batch_inv(&mut dens, p);
+
+    p1s.iter()
+        .zip(p2s)
+        .zip(dens)
+        .map(|((a, b), inv)| {
+            if a.infinity {
+                return *b;
+            }
+            if b.infinity {
+                return *a;
+            }
+            if inv == 0 {
+                // P + (-P) = O, o duplicacion de un punto con y=0
+                return Point::infinity();
+            }
+            let (x1, y1) = (Fp::new(a.x, p), Fp::new(a.y, p));
+            let (x2, y2) = (Fp::new(b.x, p), Fp::new(b.y, p));
+            let inv = Fp::new(inv, p);
+            let slope = if a.x == b.x {
+                x1.square() * 3 * inv
+            } else {
+                (y2 - y1) * inv
+            };
+            let x3 = slope.square() - x1 - x2;
+            let y3 = slope * (x1 - x3) - y1;
+            Point::from_fp(Some((x3, y3)))
+        })
+        .collect()
 }
 
 /// Negacion de un punto: -P = (x, p-y).
 pub fn point_neg(pt: &Point, p: u64) -> Point {
-    if pt.infinity {
-        return Point::infinity();
-    }
-    Point::new(pt.x, mod_sub(0, pt.y, p))
+    Point::from_fp(pt.to_fp(p).map(|(x, y)| (x, -y)))
 }
 
 /// Cuenta el numero de puntos en la curva y^2 = x^3 + b sobre F_p (fuerza bruta).
 /// Retorna n = #E(F_p) incluyendo el punto en el infinito.
 pub fn count_points(b: u64, p: u64) -> u64 {
-    let mut count = 1u64; // punto en el infinito
-    for x in 0..p {
-        let rhs = mod_add(mod_pow(x, 3, p), b, p);
-        if rhs == 0 {
-            count += 1; // y = 0
-        } else {
-            let ls = legendre(rhs, p);
-            if ls == 1 {
-                count += 2; // dos raices
-            }
+    Curve::new(0, b, p).count_points()
+}
+
+/// #E(F_p) por multiplicacion compleja (ver `math::big::cm`): O(log p) en
+/// lugar de O(p). None si p < 5 o b ≡ 0 (mod p).
+pub fn count_points_cm(b: u64, p: u64) -> Option<u64> {
+    let n = super::big::count_points_cm(&BigUint::from(b), &BigUint::from(p))?;
+    u64::try_from(n).ok()
+}
+
+/// Traza de Frobenius t = p + 1 - #E(F_p).
+pub fn trace_of_frobenius(b: u64, p: u64) -> i64 {
+    p as i64 + 1 - count_points(b, p) as i64
+}
+
+/// #E(F_{p^k}) a partir de la traza sobre F_p.
+///
+/// Si pi es el Frobenius, pi^2 - t*pi + p = 0 y #E(F_{p^k}) = p^k + 1 - s_k
+/// con s_k = pi^k + conj(pi)^k, que cumple s_0 = 2, s_1 = t y
+/// s_k = t*s_{k-1} - p*s_{k-2}.
+pub fn count_points_extension(b: u64, p: u64, k: u32) -> BigUint {
+    assert!(k >= 1, "el grado de la extension debe ser >= 1");
+    let t = BigInt::from(trace_of_frobenius(b, p));
+    let pb = BigInt::from(p);
+    let (mut s_prev, mut s) = (BigInt::from(2), t.clone());
+    for _ in 1..k {
+        let next = &t * &s - &pb * &s_prev;
+        s_prev = s;
+        s = next;
+    }
+    let n: BigInt = pb.pow(k) + 1 - s;
+    n.to_biguint().expect("#E(F_{p^k}) es positivo")
+}
+
+/// Grado de inmersion: menor k <= max_k con n | p^k - 1. None si no hay.
+pub fn embedding_degree(n: u64, p: u64, max_k: u32) -> Option<u32> {
+    let mut pk = 1u64;
+    for k in 1..=max_k {
+        pk = mod_mul(pk, p % n, n);
+        if pk == 1 % n {
+            return Some(k);
         }
     }
-    count
+    None
+}
+
+/// Todos los puntos de y^2 = x^3 + b con ordenada `y`: x recorre las raices
+/// cubicas de y^2 - b (ninguna, una o tres).
+pub fn points_with_y(y: u64, b: u64, p: u64) -> Vec<Point> {
+    let y = Fp::new(y, p);
+    let rhs = y.square() - Fp::new(b, p);
+    mod_cbrt_all(rhs.value(), p)
+        .into_iter()
+        .map(|x| Point::new(x, y.value()))
+        .collect()
 }
 
 /// Encuentra un punto generador de orden `order` en la curva y^2 = x^3 + b.
+///
+/// P tiene orden exactamente `order` si [order]P = O y [order/q]P != O
+/// para cada primo q | order (factorizado con Pollard-rho/Brent).
 pub fn find_generator(b: u64, p: u64, order: u64) -> Point {
-    for x in 1..p {
-        let rhs = mod_add(mod_pow(x, 3, p), b, p);
-        if let Some(y) = mod_sqrt(rhs, p) {
-            if y == 0 {
-                continue;
-            }
-            let pt = Point::new(x, y);
-            // Verificar que tiene orden completo
-            let check = scalar_mul(order, &pt, p);
-            if check.infinity {
-                // Verificar que no tiene un suborden trivial
-                // Chequeamos factores primos del orden
-                let mut is_generator = true;
-                let mut n = order;
-                let mut f = 2u64;
-                while f * f <= n {
-                    if n % f == 0 {
-                        if scalar_mul(order / f, &pt, p).infinity {
-                            is_generator = false;
-                            break;
-                        }
-                        while n % f == 0 {
-                            n /= f;
-                        }
-                    }
-                    f += 1;
-                }
-                if is_generator && n > 1 {
-                    if scalar_mul(order / n, &pt, p).infinity {
-                        is_generator = false;
-                    }
-                }
-                if is_generator {
-                    return pt;
-                }
-            }
-        }
-    }
-    panic!("No se encontro generador");
+    try_find_generator(b, p, order).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Como `find_generator`, con error `NoGenerator` si ningun punto tiene ese orden.
+pub fn try_find_generator(b: u64, p: u64, order: u64) -> Result<Point> {
+    Curve::new(0, b, p).try_find_generator(order)
 }
@@ -0,0 +1,109 @@
+/// Polinomios de division de y^2 = x^3 + ax + b sobre F_p.
+///
+/// psi_n se anula exactamente en los puntos de n-torsion (salvo O). Para n
+/// par psi_n lleva un factor y, asi que se trabaja con la parte en x:
+///
+///   psi_n = g_n          (n impar)
+///   psi_n = 2y * g_n     (n par)
+///
+/// con g_0 = 0, g_1 = g_2 = 1, g_3 = 3x^4 + 6ax^2 + 12bx - a^2,
+/// g_4 = 2(x^6 + 5ax^4 + 20bx^3 - 5a^2x^2 - 4abx - 8b^2 - a^3) y, llamando
+/// F = (2y)^2 = 4(x^3 + ax + b),
+///
+///   g_{2m+1} = F^2 g_{m+2} g_m^3 - g_{m-1} g_{m+1}^3     (m par)
+///   g_{2m+1} = g_{m+2} g_m^3 - F^2 g_{m-1} g_{m+1}^3     (m impar)
+///   g_{2m}   = g_m (g_{m+2} g_{m-1}^2 - g_{m-2} g_{m+1}^2)
+///
+/// Para l impar, g_l tiene grado (l^2 - 1)/2 y coeficiente principal l.
+/// `division_polynomial_squared` da psi_n^2, que si es un polinomio en x
+/// para todo n, y `torsion_polynomial` el que enumera las abscisas de E[n].
+
+use super::field::*;
+use super::poly::Poly;
+use super::weierstrass::Curve;
+
+/// g_0, ..., g_n de la curva (ver la convencion del modulo).
+pub fn division_polynomials(ec: &Curve, n: usize) -> Vec<Poly> {
+    let (a, b, p) = (ec.a, ec.b, ec.p);
+    let c = |v: u64| v % p;
+    let neg = |v: u64| mod_sub(0, v % p, p);
+    let a2 = mod_mul(a, a, p);
+    let a3 = mod_mul(a2, a, p);
+    let ab = mod_mul(a, b, p);
+    let b2 = mod_mul(b, b, p);
+
+    let mut g = vec![
+        Poly::zero(p),
+        Poly::one(p),
+        Poly::one(p),
+        Poly::new(vec![neg(a2), mod_mul(12, b, p), mod_mul(6, a, p), 0, c(3)], p),
+        Poly::new(vec![
+            mod_sub(0, mod_mul(2, mod_add(mod_mul(8, b2, p), a3, p), p), p),
+            neg(mod_mul(8, ab, p)),
+            neg(mod_mul(10, a2, p)),
+            mod_mul(40, b, p),
+            mod_mul(10, a, p),
+            0,
+            c(2),
+        ], p),
+    ];
+    let f = Poly::new(vec![mod_mul(4, b, p), mod_mul(4, a, p), 0, c(4)], p);
+    let f2 = &f * &f;
+    let cube = |q: &Poly| &(q * q) * q;
+    let square = |q: &Poly| q * q;
+
+    for i in g.len()..=n {
+        let m = i / 2;
+        let next = if i % 2 == 1 {
+            let left = &g[m + 2] * &cube(&g[m]);
+            let right = &g[m - 1] * &cube(&g[m + 1]);
+            if m % 2 == 0 {
+                &(&f2 * &left) - &right
+            } else {
+                &left - &(&f2 * &right)
+            }
+        } else {
+            let left = &g[m + 2] * &square(&g[m - 1]);
+            let right = &g[m - 2] * &square(&g[m + 1]);
+            &g[m] * &(&left - &right)
+        };
+        g.push(next);
+    }
+    g.truncate(n + 1);
+    g
+}
+
+/// g_n: psi_n para n impar, psi_n / 2y para n par.
+pub fn division_polynomial(ec: &Curve, n: usize) -> Poly {
+    division_polynomials(ec, n).pop().expect("n + 1 polinomios")
+}
+
+/// x^3 + ax + b, con F = 4 (x^3 + ax + b) = (2y)^2.
+fn curve_rhs(ec: &Curve) -> Poly {
+    Poly::new(vec![ec.b, ec.a, 0, 1], ec.p)
+}
+
+/// psi_n^2 como polinomio en x: g_n^2 para n impar y F g_n^2 para n par.
+/// Grado n^2 - 1; sus ceros son las abscisas de E[n] \ {O}.
+pub fn division_polynomial_squared(ec: &Curve, n: usize) -> Poly {
+    let g = division_polynomial(ec, n);
+    let g2 = &g * &g;
+    if n % 2 == 1 {
+        g2
+    } else {
+        &curve_rhs(ec).scale(4) * &g2
+    }
+}
+
+/// Polinomio en x sin factores repetidos por y: g_n para n impar y
+/// (x^3 + ax + b) g_n para n par. Sus raices en F_p son las abscisas
+/// racionales de los puntos de E[n] \ {O} (los de y = 0 vienen del factor
+/// x^3 + ax + b).
+pub fn torsion_polynomial(ec: &Curve, n: usize) -> Poly {
+    let g = division_polynomial(ec, n);
+    if n % 2 == 1 {
+        g
+    } else {
+        &curve_rhs(ec) * &g
+    }
+}
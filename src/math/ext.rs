@@ -0,0 +1,297 @@
+/// Extensiones F_{p^k} = F_p[x] / (f(x)) en base polinomial.
+///
+/// f es monico e irreducible de grado K; un elemento es a_0 + a_1 x + ... +
+/// a_{K-1} x^{K-1}. Con `Field` implementado, la ley de grupo generica de
+/// `math::curve` trabaja sobre E(F_{p^k}) sin cambios: grado de inmersion,
+/// mapas de distorsion y el caso supersingular p ≡ 2 (mod 3) necesitan
+/// puntos fuera de F_p.
+
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use num_bigint::BigUint;
+use num_traits::One;
+
+use super::field::*;
+use super::fp::Field;
+use super::poly::Poly;
+
+/// Test de irreducibilidad de Rabin para f monico de grado k:
+/// x^(p^k) ≡ x (mod f) y mcd(x^(p^(k/q)) - x, f) = 1 para cada primo q | k.
+fn is_irreducible(f: &Poly) -> bool {
+    let p = f.modulus();
+    let k = f.degree().expect("f no es nulo");
+    let x = Poly::x(p);
+    // k / q para cada primo q | k
+    let maximal: Vec<usize> = (2..=k)
+        .filter(|&q| k.is_multiple_of(q) && (2..q).all(|d| q % d != 0))
+        .map(|q| k / q)
+        .collect();
+    let mut h = x.clone();
+    for i in 1..=k {
+        h = h.pow_mod(p, f);
+        if maximal.contains(&i) && (&h - &x).gcd(f) != Poly::one(p) {
+            return false;
+        }
+    }
+    (&h - &x).rem(f).is_zero()
+}
+
+/// Coeficientes bajos de un polinomio monico irreducible de grado K sobre F_p:
+/// f(x) = x^K + m[K-1] x^(K-1) + ... + m[0]. Recorre candidatos en orden
+/// creciente de coeficientes, empezando por los binomios x^K + m0.
+pub fn find_irreducible<const K: usize>(p: u64) -> [u64; K] {
+    assert!(K >= 1, "el grado de la extension debe ser >= 1");
+    let mut n = 1u64;
+    loop {
+        // digitos de n en base p, con m0 != 0 (si no, x divide a f)
+        let mut m = [0u64; K];
+        let mut rest = n;
+        for c in m.iter_mut() {
+            *c = rest % p;
+            rest /= p;
+        }
+        if m[0] != 0 {
+            let mut f = m.to_vec();
+            f.push(1);
+            if is_irreducible(&Poly::new(f, p)) {
+                return m;
+            }
+        }
+        n += 1;
+    }
+}
+
+/// Elemento de F_{p^K} en base polinomial.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Fpk<const K: usize> {
+    coeffs: [u64; K],
+    /// coeficientes bajos del modulo monico f(x) = x^K + ...
+    modulus: [u64; K],
+    p: u64,
+}
+
+impl<const K: usize> Fpk<K> {
+    /// a_0 + a_1 x + ... modulo f(x) = x^K + sum modulus[i] x^i. El
+    /// polinomio debe ser irreducible sobre F_p (ver `find_irreducible`).
+    pub fn new(coeffs: [u64; K], modulus: [u64; K], p: u64) -> Self {
+        Fpk { coeffs: coeffs.map(|c| c % p), modulus: modulus.map(|c| c % p), p }
+    }
+
+    /// Cero de F_{p^K} usando el primer irreducible de `find_irreducible`.
+    pub fn with_default_modulus(p: u64) -> Self {
+        Fpk::new([0; K], find_irreducible::<K>(p), p)
+    }
+
+    /// Elemento con los coeficientes dados en el mismo cuerpo que `self`.
+    pub fn with(&self, coeffs: [u64; K]) -> Self {
+        Fpk::new(coeffs, self.modulus, self.p)
+    }
+
+    /// La clase de x, que genera F_{p^K} sobre F_p.
+    pub fn generator(&self) -> Self {
+        let mut c = [0u64; K];
+        if K == 1 {
+            c[0] = mod_sub(0, self.modulus[0], self.p);
+        } else {
+            c[1] = 1;
+        }
+        self.with(c)
+    }
+
+    pub fn coeffs(&self) -> [u64; K] {
+        self.coeffs
+    }
+
+    pub fn modulus(&self) -> [u64; K] {
+        self.modulus
+    }
+
+    pub fn characteristic(&self) -> u64 {
+        self.p
+    }
+
+    /// q = p^K, el orden del cuerpo.
+    pub fn order(&self) -> BigUint {
+        BigUint::from(self.p).pow(K as u32)
+    }
+
+    /// True si el elemento pertenece al subcuerpo primo F_p.
+    pub fn is_base(&self) -> bool {
+        self.coeffs[1..].iter().all(|&c| c == 0)
+    }
+
+    /// Frobenius a -> a^p.
+    pub fn frobenius(&self) -> Self {
+        self.pow(self.p)
+    }
+
+    /// Exponenciacion con exponente arbitrario (p^K no cabe en u64).
+    pub fn pow_big(&self, exp: &BigUint) -> Self {
+        let mut result = self.one();
+        for i in (0..exp.bits()).rev() {
+            result = result.square();
+            if exp.bit(i) {
+                result = result * *self;
+            }
+        }
+        result
+    }
+
+    fn to_poly(self) -> Poly {
+        Poly::new(self.coeffs.to_vec(), self.p)
+    }
+
+    fn modulus_poly(&self) -> Poly {
+        let mut f = self.modulus.to_vec();
+        f.push(1);
+        Poly::new(f, self.p)
+    }
+
+    /// Elemento de un polinomio de grado < K.
+    fn with_poly(&self, r: &Poly) -> Self {
+        let mut c = [0u64; K];
+        c[..r.coeffs().len()].copy_from_slice(r.coeffs());
+        self.with(c)
+    }
+}
+
+impl<const K: usize> Add for Fpk<K> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        let mut c = self.coeffs;
+        for (a, b) in c.iter_mut().zip(rhs.coeffs) {
+            *a = mod_add(*a, b, self.p);
+        }
+        self.with(c)
+    }
+}
+
+impl<const K: usize> Sub for Fpk<K> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        let mut c = self.coeffs;
+        for (a, b) in c.iter_mut().zip(rhs.coeffs) {
+            *a = mod_sub(*a, b, self.p);
+        }
+        self.with(c)
+    }
+}
+
+impl<const K: usize> Mul for Fpk<K> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        self.with_poly(&self.to_poly().mul_mod(&rhs.to_poly(), &self.modulus_poly()))
+    }
+}
+
+impl<const K: usize> Neg for Fpk<K> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self.zero() - self
+    }
+}
+
+impl<const K: usize> Field for Fpk<K> {
+    fn zero(&self) -> Self {
+        self.with([0; K])
+    }
+
+    fn one(&self) -> Self {
+        self.lift(1)
+    }
+
+    fn lift(&self, v: u64) -> Self {
+        let mut c = [0u64; K];
+        c[0] = v;
+        self.with(c)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.coeffs.iter().all(|&c| c == 0)
+    }
+
+    /// Inverso por Euclides extendido: s*a + t*f = 1 implica a^(-1) = s.
+    fn inv(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        // mcd(a, f) = 1 si f es irreducible
+        let s = self.to_poly().inv_mod(&self.modulus_poly())?;
+        Some(self.with_poly(&s))
+    }
+
+    /// Tonelli-Shanks sobre q = p^K (con exponentes BigUint).
+    fn sqrt(&self) -> Option<Self> {
+        if self.is_zero() {
+            return Some(*self);
+        }
+        let q = self.order();
+        let one = BigUint::one();
+        if self.p == 2 {
+            // caracteristica 2: el Frobenius es biyectivo, sqrt(a) = a^(q/2)
+            return Some(self.pow_big(&(&q >> 1)));
+        }
+        let q1 = &q - &one;
+        if self.pow_big(&(&q1 >> 1)) != self.one() {
+            return None;
+        }
+
+        // q - 1 = 2^s * t con t impar
+        let s = q1.trailing_zeros().unwrap_or(0);
+        let t = &q1 >> s;
+
+        // No-residuo: primer elemento x + c con z^((q-1)/2) = -1
+        let minus_one = -self.one();
+        let z = (0..self.p)
+            .map(|c| self.generator() + self.lift(c))
+            .chain((2..self.p).map(|c| self.lift(c)))
+            .find(|z| z.pow_big(&(&q1 >> 1)) == minus_one)?;
+
+        let mut m = s;
+        let mut c = z.pow_big(&t);
+        let mut tt = self.pow_big(&t);
+        let mut r = self.pow_big(&((&t + &one) >> 1));
+        while tt != self.one() {
+            let mut i = 0;
+            let mut tmp = tt;
+            while tmp != self.one() {
+                tmp = tmp.square();
+                i += 1;
+                if i == m {
+                    return None;
+                }
+            }
+            let mut b = c;
+            for _ in 0..m - i - 1 {
+                b = b.square();
+            }
+            m = i;
+            c = b.square();
+            tt = tt * c;
+            r = r * b;
+        }
+        Some(r)
+    }
+}
+
+impl<const K: usize> fmt::Display for Fpk<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let terms: Vec<String> = self
+            .coeffs
+            .iter()
+            .enumerate()
+            .filter(|(_, &c)| c != 0)
+            .map(|(i, c)| match i {
+                0 => format!("{}", c),
+                1 => format!("{}*x", c),
+                _ => format!("{}*x^{}", c, i),
+            })
+            .collect();
+        if terms.is_empty() {
+            write!(f, "0")
+        } else {
+            write!(f, "{}", terms.join(" + "))
+        }
+    }
+}
@@ -1,5 +1,7 @@
 /// Aritmetica modular sobre F_p usando u64 (sin dependencias externas).
 
+use crate::error::{Error, Result};
+
 pub fn mod_add(a: u64, b: u64, p: u64) -> u64 {
     ((a as u128 + b as u128) % p as u128) as u64
 }
@@ -29,9 +31,16 @@ pub fn mod_pow(base: u64, mut exp: u64, p: u64) -> u64 {
     result as u64
 }
 
-/// Inverso modular via extended GCD. Panics si a == 0.
+/// Inverso modular via extended GCD. Panics si gcd(a, p) != 1 (ver `try_mod_inv`).
 pub fn mod_inv(a: u64, p: u64) -> u64 {
-    let a = a as i128;
+    try_mod_inv(a, p).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Inverso modular via extended GCD. Error `NotInvertible` con el gcd si
+/// gcd(a, p) != 1; p no necesita ser primo.
+pub fn try_mod_inv(a: u64, p: u64) -> Result<u64> {
+    let (value, modulus) = (a, p);
+    let a = (a % p) as i128;
     let p = p as i128;
     let (mut old_r, mut r) = (a, p);
     let (mut old_s, mut s) = (1i128, 0i128);
@@ -46,7 +55,38 @@ pub fn mod_inv(a: u64, p: u64) -> u64 {
         old_s = tmp;
     }
 
-    ((old_s % p + p) % p) as u64
+    // old_r = gcd(a, p); con a ≡ 0 queda old_r = p
+    if old_r != 1 {
+        return Err(Error::NotInvertible { value, modulus, gcd: old_r as u64 });
+    }
+    Ok(((old_s % p + p) % p) as u64)
+}
+
+/// Inversion simultanea (truco de Montgomery): reemplaza cada valor por su inverso.
+///
+/// Cuesta un solo `mod_inv` mas 3(n-1) multiplicaciones, en lugar de n
+/// extended GCD. Los valores ≡ 0 (mod p) no tienen inverso y se dejan en 0.
+pub fn batch_inv(values: &mut [u64], p: u64) {
+    // prefix[i] = producto de los valores no nulos anteriores a i
+    let mut prefix = Vec::with_capacity(values.len());
+    let mut acc = 1u64;
+    for v in values.iter_mut() {
+        *v %= p;
+        prefix.push(acc);
+        if *v != 0 {
+            acc = mod_mul(acc, *v, p);
+        }
+    }
+
+    let mut inv = mod_inv(acc, p);
+    for (v, pre) in values.iter_mut().zip(prefix).rev() {
+        if *v == 0 {
+            continue;
+        }
+        let orig = *v;
+        *v = mod_mul(inv, pre, p);
+        inv = mod_mul(inv, orig, p);
+    }
 }
 
 /// Raiz cuadrada modular via Tonelli-Shanks. Retorna None si `a` no es QR.
@@ -107,3 +147,99 @@ pub fn mod_sqrt(a: u64, p: u64) -> Option<u64> {
 pub fn legendre(a: u64, p: u64) -> u64 {
     mod_pow(a % p, (p - 1) / 2, p)
 }
+
+/// Contexto de reduccion de Montgomery para un primo impar p < 2^64 (R = 2^64).
+///
+/// Se crea una vez por curva: en forma de Montgomery (a*R mod p) cada
+/// producto se reduce con REDC, que solo usa multiplicaciones y un
+/// desplazamiento en lugar de la division `u128 % p` de `mod_mul`.
+///
+/// Sirve para todo el rango u64: con p >= 2^63 la suma en REDC y la de
+/// `add` pueden pasar de 2^128 y 2^64, y se recupera el acarreo.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MontgomeryCtx {
+    pub p: u64,
+    /// -p^(-1) mod 2^64
+    p_inv_neg: u64,
+    /// R^2 mod p, para convertir a forma de Montgomery
+    r2: u64,
+    /// R mod p, el 1 en forma de Montgomery
+    one: u64,
+}
+
+impl MontgomeryCtx {
+    pub fn new(p: u64) -> Self {
+        assert!(p % 2 == 1, "Montgomery requiere p impar");
+
+        // Newton-Hensel: cada iteracion duplica los bits correctos de p^(-1) mod 2^64
+        let mut inv = 1u64;
+        for _ in 0..6 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(p.wrapping_mul(inv)));
+        }
+
+        let r = ((1u128 << 64) % p as u128) as u64;
+        let r2 = (r as u128 * r as u128 % p as u128) as u64;
+        MontgomeryCtx { p, p_inv_neg: inv.wrapping_neg(), r2, one: r }
+    }
+
+    /// REDC: t * R^(-1) mod p, para t < p * 2^64.
+    #[inline(always)]
+    fn redc(&self, t: u128) -> u64 {
+        let m = (t as u64).wrapping_mul(self.p_inv_neg);
+        let (sum, carry) = t.overflowing_add(m as u128 * self.p as u128);
+        // u = (t + m p) / R < 2p, que puede necesitar 65 bits
+        let u = (sum >> 64) | ((carry as u128) << 64);
+        let p = self.p as u128;
+        (if u >= p { u - p } else { u }) as u64
+    }
+
+    /// a -> a*R mod p
+    pub fn to_mont(&self, a: u64) -> u64 {
+        self.mul(a % self.p, self.r2)
+    }
+
+    /// a*R -> a mod p
+    pub fn from_mont(&self, a: u64) -> u64 {
+        self.redc(a as u128)
+    }
+
+    /// El 1 en forma de Montgomery.
+    pub fn one(&self) -> u64 {
+        self.one
+    }
+
+    #[inline(always)]
+    pub fn mul(&self, a: u64, b: u64) -> u64 {
+        self.redc(a as u128 * b as u128)
+    }
+
+    #[inline(always)]
+    pub fn add(&self, a: u64, b: u64) -> u64 {
+        let (s, carry) = a.overflowing_add(b);
+        if carry || s >= self.p { s.wrapping_sub(self.p) } else { s }
+    }
+
+    #[inline(always)]
+    pub fn sub(&self, a: u64, b: u64) -> u64 {
+        if a >= b { a - b } else { a.wrapping_sub(b).wrapping_add(self.p) }
+    }
+
+    /// Exponenciacion en forma de Montgomery (base y resultado en forma de Montgomery).
+    pub fn pow(&self, base: u64, mut exp: u64) -> u64 {
+        let mut result = self.one;
+        let mut b = base;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = self.mul(result, b);
+            }
+            exp >>= 1;
+            b = self.mul(b, b);
+        }
+        result
+    }
+
+    /// Inverso por Fermat, a^(p-2), en forma de Montgomery. Requiere p primo.
+    pub fn inv(&self, a: u64) -> u64 {
+        self.pow(a, self.p - 2)
+    }
+}
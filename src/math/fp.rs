@@ -0,0 +1,264 @@
+/// Elementos tipados de cuerpo primo: coordenadas en F_p y escalares en Z/nZ.
+///
+/// `Fp` y `Scalar` llevan su modulo junto al valor y son tipos distintos,
+/// asi que el compilador impide mezclar una coordenada (mod p) con un
+/// coeficiente de logaritmo discreto (mod n). Las operaciones delegan en
+/// las funciones libres de `math::field`.
+
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use super::field::*;
+
+/// Interfaz comun de un elemento de cuerpo finito (primo o extension).
+///
+/// Los constructores (`zero`, `one`, `lift`) toman `&self` porque el
+/// modulo puede ser un dato de ejecucion: devuelven un elemento del mismo
+/// cuerpo que `self`.
+pub trait Field:
+    Copy
+    + PartialEq
+    + Eq
+    + fmt::Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    fn zero(&self) -> Self;
+    fn one(&self) -> Self;
+    fn lift(&self, v: u64) -> Self;
+    fn is_zero(&self) -> bool;
+
+    /// Inverso multiplicativo. None si el elemento es 0 (o, con modulo
+    /// compuesto, si no es coprimo con el).
+    fn inv(&self) -> Option<Self>;
+
+    /// Raiz cuadrada. None si el elemento no es residuo cuadratico.
+    fn sqrt(&self) -> Option<Self>;
+
+    fn square(&self) -> Self {
+        *self * *self
+    }
+
+    fn double(&self) -> Self {
+        *self + *self
+    }
+
+    /// Exponenciacion por square-and-multiply.
+    fn pow(&self, mut exp: u64) -> Self {
+        let mut result = self.one();
+        let mut base = *self;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result * base;
+            }
+            base = base.square();
+            exp >>= 1;
+        }
+        result
+    }
+}
+
+/// Define un tipo de elemento modular sobre u64 con sus operadores.
+macro_rules! modular_element {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub struct $name {
+            value: u64,
+            modulus: u64,
+        }
+
+        impl $name {
+            /// Construye el elemento reduciendo `value` modulo `modulus`.
+            pub fn new(value: u64, modulus: u64) -> Self {
+                $name { value: value % modulus, modulus }
+            }
+
+            /// Representante canonico en [0, modulus).
+            pub fn value(&self) -> u64 {
+                self.value
+            }
+
+            pub fn modulus(&self) -> u64 {
+                self.modulus
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                debug_assert_eq!(self.modulus, rhs.modulus, "modulos distintos");
+                $name { value: mod_add(self.value, rhs.value, self.modulus), modulus: self.modulus }
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self {
+                debug_assert_eq!(self.modulus, rhs.modulus, "modulos distintos");
+                $name { value: mod_sub(self.value, rhs.value, self.modulus), modulus: self.modulus }
+            }
+        }
+
+        impl Mul for $name {
+            type Output = Self;
+            fn mul(self, rhs: Self) -> Self {
+                debug_assert_eq!(self.modulus, rhs.modulus, "modulos distintos");
+                $name { value: mod_mul(self.value, rhs.value, self.modulus), modulus: self.modulus }
+            }
+        }
+
+        impl Mul<u64> for $name {
+            type Output = Self;
+            fn mul(self, rhs: u64) -> Self {
+                self * $name::new(rhs, self.modulus)
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+            fn neg(self) -> Self {
+                $name { value: mod_sub(0, self.value, self.modulus), modulus: self.modulus }
+            }
+        }
+
+        impl Field for $name {
+            fn zero(&self) -> Self {
+                $name { value: 0, modulus: self.modulus }
+            }
+
+            fn one(&self) -> Self {
+                $name::new(1, self.modulus)
+            }
+
+            fn lift(&self, v: u64) -> Self {
+                $name::new(v, self.modulus)
+            }
+
+            fn is_zero(&self) -> bool {
+                self.value == 0
+            }
+
+            fn inv(&self) -> Option<Self> {
+                let inv = try_mod_inv(self.value, self.modulus).ok()?;
+                Some($name { value: inv, modulus: self.modulus })
+            }
+
+            fn sqrt(&self) -> Option<Self> {
+                mod_sqrt(self.value, self.modulus)
+                    .map(|r| $name { value: r, modulus: self.modulus })
+            }
+
+            fn pow(&self, exp: u64) -> Self {
+                $name { value: mod_pow(self.value, exp, self.modulus), modulus: self.modulus }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}", self.value)
+            }
+        }
+    };
+}
+
+modular_element! {
+    /// Elemento de F_p: coordenada de un punto de la curva.
+    Fp
+}
+
+modular_element! {
+    /// Elemento de Z/nZ: escalar o coeficiente de un logaritmo discreto,
+    /// con n el orden del grupo (o del subgrupo) de la curva.
+    Scalar
+}
+
+/// Elemento de F_p guardado en forma de Montgomery junto a su contexto.
+///
+/// Mismo cuerpo que `Fp`, pero cada producto usa REDC en lugar de `u128 % p`
+/// y el inverso es a^(p-2) en vez del extended GCD.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MontFp {
+    mont: u64,
+    ctx: MontgomeryCtx,
+}
+
+impl MontFp {
+    pub fn new(value: u64, ctx: &MontgomeryCtx) -> Self {
+        MontFp { mont: ctx.to_mont(value), ctx: *ctx }
+    }
+
+    /// Representante canonico en [0, p), fuera de la forma de Montgomery.
+    pub fn value(&self) -> u64 {
+        self.ctx.from_mont(self.mont)
+    }
+}
+
+impl Add for MontFp {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        MontFp { mont: self.ctx.add(self.mont, rhs.mont), ctx: self.ctx }
+    }
+}
+
+impl Sub for MontFp {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        MontFp { mont: self.ctx.sub(self.mont, rhs.mont), ctx: self.ctx }
+    }
+}
+
+impl Mul for MontFp {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        MontFp { mont: self.ctx.mul(self.mont, rhs.mont), ctx: self.ctx }
+    }
+}
+
+impl Neg for MontFp {
+    type Output = Self;
+    fn neg(self) -> Self {
+        MontFp { mont: self.ctx.sub(0, self.mont), ctx: self.ctx }
+    }
+}
+
+impl Field for MontFp {
+    fn zero(&self) -> Self {
+        MontFp { mont: 0, ctx: self.ctx }
+    }
+
+    fn one(&self) -> Self {
+        MontFp { mont: self.ctx.one(), ctx: self.ctx }
+    }
+
+    fn lift(&self, v: u64) -> Self {
+        MontFp::new(v, &self.ctx)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.mont == 0
+    }
+
+    fn inv(&self) -> Option<Self> {
+        if self.mont == 0 {
+            return None;
+        }
+        Some(MontFp { mont: self.ctx.inv(self.mont), ctx: self.ctx })
+    }
+
+    fn sqrt(&self) -> Option<Self> {
+        mod_sqrt(self.value(), self.ctx.p).map(|r| MontFp::new(r, &self.ctx))
+    }
+
+    fn pow(&self, exp: u64) -> Self {
+        MontFp { mont: self.ctx.pow(self.mont, exp), ctx: self.ctx }
+    }
+}
+
+impl fmt::Display for MontFp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.value())
+    }
+}
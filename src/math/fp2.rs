@@ -0,0 +1,179 @@
+/// Extension cuadratica F_p^2 = F_p(sqrt(nr)), con nr un no-residuo cuadratico.
+///
+/// Un elemento es c0 + c1*i con i^2 = nr. Sirve para extraer raices
+/// cuadradas de elementos de F_p que no son residuos, y de F_p^2 en general.
+
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use super::fp::*;
+use super::roots::quadratic_non_residue;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Fp2 {
+    pub c0: Fp,
+    pub c1: Fp,
+    /// nr = i^2, fijo para todo el cuerpo
+    nr: Fp,
+}
+
+impl Fp2 {
+    /// c0 + c1*i con i^2 = nr. `nr` debe ser no-residuo cuadratico mod p.
+    pub fn new(c0: u64, c1: u64, nr: u64, p: u64) -> Self {
+        Fp2 { c0: Fp::new(c0, p), c1: Fp::new(c1, p), nr: Fp::new(nr, p) }
+    }
+
+    /// c0 + c1*i usando el menor no-residuo cuadratico de F_p como nr.
+    pub fn with_default_nr(c0: u64, c1: u64, p: u64) -> Option<Self> {
+        let nr = quadratic_non_residue(p)?;
+        Some(Fp2::new(c0, c1, nr, p))
+    }
+
+    /// Elemento de F_p^2 con la misma i que `self`.
+    pub fn with(&self, c0: Fp, c1: Fp) -> Self {
+        Fp2 { c0, c1, nr: self.nr }
+    }
+
+    pub fn nr(&self) -> Fp {
+        self.nr
+    }
+
+    pub fn zero(&self) -> Self {
+        self.with(self.c0.zero(), self.c0.zero())
+    }
+
+    pub fn one(&self) -> Self {
+        self.with(self.c0.one(), self.c0.zero())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.c0.is_zero() && self.c1.is_zero()
+    }
+
+    /// Conjugado c0 - c1*i (imagen por Frobenius x -> x^p).
+    pub fn conj(&self) -> Self {
+        self.with(self.c0, -self.c1)
+    }
+
+    /// Norma N(a) = a * conj(a) = c0^2 - nr*c1^2, en F_p.
+    pub fn norm(&self) -> Fp {
+        self.c0.square() - self.nr * self.c1.square()
+    }
+
+    pub fn square(&self) -> Self {
+        *self * *self
+    }
+
+    /// Inverso conj(a) / N(a). None si a == 0.
+    pub fn inv(&self) -> Option<Self> {
+        let n_inv = self.norm().inv()?;
+        Some(self.with(self.c0 * n_inv, -self.c1 * n_inv))
+    }
+
+    pub fn pow(&self, mut exp: u64) -> Self {
+        let mut result = self.one();
+        let mut base = *self;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result * base;
+            }
+            base = base.square();
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// Raiz cuadrada en F_p^2. None si el elemento no es cuadrado.
+    ///
+    /// Todo elemento de F_p es cuadrado en F_p^2. En general, a es cuadrado
+    /// sii su norma lo es en F_p; con alpha = sqrt(N(a)) la raiz es
+    /// x0 + x1*i con x0^2 = (c0 ± alpha)/2 y x1 = c1 / (2*x0).
+    pub fn sqrt(&self) -> Option<Self> {
+        if self.c1.is_zero() {
+            if let Some(r) = self.c0.sqrt() {
+                return Some(self.with(r, r.zero()));
+            }
+            // c0 no es residuo, pero c0/nr si: (s*i)^2 = s^2 * nr = c0
+            let s = (self.c0 * self.nr.inv()?).sqrt()?;
+            return Some(self.with(s.zero(), s));
+        }
+
+        let alpha = self.norm().sqrt()?;
+        let half = self.c0.lift(2).inv()?;
+        let delta = (self.c0 + alpha) * half;
+        let x0 = match delta.sqrt() {
+            Some(x0) => x0,
+            None => ((self.c0 - alpha) * half).sqrt()?,
+        };
+        let x1 = self.c1 * x0.double().inv()?;
+        let root = self.with(x0, x1);
+        if root.square() == *self {
+            Some(root)
+        } else {
+            None
+        }
+    }
+}
+
+impl Field for Fp2 {
+    fn zero(&self) -> Self {
+        Fp2::zero(self)
+    }
+
+    fn one(&self) -> Self {
+        Fp2::one(self)
+    }
+
+    fn lift(&self, v: u64) -> Self {
+        self.with(self.c0.lift(v), self.c0.zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        Fp2::is_zero(self)
+    }
+
+    fn inv(&self) -> Option<Self> {
+        Fp2::inv(self)
+    }
+
+    fn sqrt(&self) -> Option<Self> {
+        Fp2::sqrt(self)
+    }
+}
+
+impl Add for Fp2 {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        self.with(self.c0 + rhs.c0, self.c1 + rhs.c1)
+    }
+}
+
+impl Sub for Fp2 {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        self.with(self.c0 - rhs.c0, self.c1 - rhs.c1)
+    }
+}
+
+impl Mul for Fp2 {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        // (a0 + a1 i)(b0 + b1 i) = a0 b0 + nr a1 b1 + (a0 b1 + a1 b0) i
+        let c0 = self.c0 * rhs.c0 + self.nr * self.c1 * rhs.c1;
+        let c1 = self.c0 * rhs.c1 + self.c1 * rhs.c0;
+        self.with(c0, c1)
+    }
+}
+
+impl Neg for Fp2 {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self.with(-self.c0, -self.c1)
+    }
+}
+
+impl fmt::Display for Fp2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} + {}*i", self.c0, self.c1)
+    }
+}
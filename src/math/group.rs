@@ -0,0 +1,227 @@
+/// Estructura de E(F_p) como grupo abeliano: E ≅ Z/n1 x Z/n2 con n1 | n2
+/// y n1 | p - 1, junto a una base (P1, P2).
+///
+/// `find_generator` supone E ciclico; en y^2 = x^3 + b deja de serlo en
+/// cuanto E[2] o E[3] es racional. Aqui:
+///
+/// 1. P1 es un punto de orden maximo (el exponente n2), combinando los
+///    ordenes de varios puntos primo a primo.
+/// 2. Para cada primo q | n1 = #E / n2, dentro del q-Sylow (Z/q^a x Z/q^b,
+///    a <= b) se resta a un punto S su componente en <P1> resolviendo
+///    [q^a] S = [z] P1_q por Pohlig-Hellman; lo que queda tiene orden q^a
+///    y, si su subgrupo de orden q no esta en <P1>, es un generador del
+///    otro sumando.
+/// 3. P2 = suma de esos generadores. <P1> ∩ <P2> = O y |<P1>| |<P2>| = #E
+///    certifican la descomposicion; si P1 aun no tenia orden maximo, el
+///    paso 2 falla y se sigue muestreando.
+///
+/// Los logaritmos en subgrupos de orden q cuestan O(q); como q | n1 y
+/// n1^2 | #E, q <= sqrt(#E) y en la practica es muy pequeno.
+
+use std::fmt;
+
+use num_integer::Integer;
+
+use crate::error::{Error, Result};
+use super::curve::Point;
+use super::field::*;
+use super::primes::*;
+use super::weierstrass::Curve;
+
+/// E(F_p) ≅ Z/n1 x Z/n2 con base (P1, P2): ord(P1) = n2, ord(P2) = n1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GroupStructure {
+    pub n1: u64,
+    pub n2: u64,
+    pub p1: Point,
+    pub p2: Point,
+}
+
+impl GroupStructure {
+    pub fn is_cyclic(&self) -> bool {
+        self.n1 == 1
+    }
+
+    /// #E = n1 * n2.
+    pub fn order(&self) -> u64 {
+        self.n1 * self.n2
+    }
+}
+
+impl fmt::Display for GroupStructure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_cyclic() {
+            write!(f, "Z/{}", self.n2)
+        } else {
+            write!(f, "Z/{} x Z/{}", self.n1, self.n2)
+        }
+    }
+}
+
+/// Reduce un multiplo m de ord(P) (con [m]P = O) al orden exacto,
+/// quitando cada primo q | m mientras [m/q]P siga siendo O.
+pub fn order_from_multiple(ec: &Curve, pt: &Point, m: u64) -> u64 {
+    let mut order = m;
+    for (q, e) in factor(m) {
+        for _ in 0..e {
+            if !ec.scalar_mul(order / q, pt).infinity {
+                break;
+            }
+            order /= q;
+        }
+    }
+    order
+}
+
+/// Exponente de q en n.
+fn valuation(mut n: u64, q: u64) -> u32 {
+    let mut v = 0;
+    while n.is_multiple_of(q) {
+        n /= q;
+        v += 1;
+    }
+    v
+}
+
+/// Puntos finitos de la curva por x creciente.
+fn curve_points(ec: &Curve) -> impl Iterator<Item = Point> + Clone + '_ {
+    let p = ec.p;
+    (0..p).filter_map(move |x| {
+        let rhs = mod_add(mod_add(mod_pow(x, 3, p), mod_mul(ec.a, x, p), p), ec.b, p);
+        mod_sqrt(rhs, p).map(|y| Point::new(x, y))
+    })
+}
+
+/// Punto de orden lcm(ord P, ord Q): para cada primo toma la parte de
+/// mayor orden de P o de Q y las suma (ordenes coprimos).
+fn combine(ec: &Curve, (pt, m): (Point, u64), (qt, n): (Point, u64)) -> (Point, u64) {
+    let mut acc = Point::infinity();
+    let mut order = 1;
+    for q in prime_factors(m.lcm(&n)) {
+        let (vm, vn) = (valuation(m, q), valuation(n, q));
+        let (base, ord, v) = if vm >= vn { (&pt, m, vm) } else { (&qt, n, vn) };
+        let qv = q.pow(v);
+        acc = ec.point_add(&acc, &ec.scalar_mul(ord / qv, base));
+        order *= qv;
+    }
+    (acc, order)
+}
+
+/// d en [0, q) con target = [d] gamma (gamma de orden q). O(q).
+fn dlog_prime(ec: &Curve, target: &Point, gamma: &Point, q: u64) -> Option<u64> {
+    let mut acc = Point::infinity();
+    for d in 0..q {
+        if acc == *target {
+            return Some(d);
+        }
+        acc = ec.point_add(&acc, gamma);
+    }
+    None
+}
+
+/// z con target = [z] base, base de orden q^b (Pohlig-Hellman digito a digito).
+fn dlog_prime_power(ec: &Curve, target: &Point, base: &Point, q: u64, b: u32) -> Option<u64> {
+    let gamma = ec.scalar_mul(q.pow(b - 1), base);
+    let mut z = 0u64;
+    for k in 0..b {
+        let rest = ec.point_add(target, &ec.point_neg(&ec.scalar_mul(z, base)));
+        let d = dlog_prime(ec, &ec.scalar_mul(q.pow(b - 1 - k), &rest), &gamma, q)?;
+        z += d * q.pow(k);
+    }
+    Some(z)
+}
+
+/// Generador del sumando Z/q^a del q-Sylow complementario a <A> (ord A = q^b),
+/// a partir de un punto S del q-Sylow. None si S no sirve.
+fn sylow_complement(ec: &Curve, s: &Point, a_pt: &Point, q: u64, a: u32, b: u32) -> Option<Point> {
+    let qa = q.pow(a);
+    let z = dlog_prime_power(ec, &ec.scalar_mul(qa, s), a_pt, q, b)?;
+    if !z.is_multiple_of(qa) {
+        return None;
+    }
+    let c = ec.point_add(s, &ec.point_neg(&ec.scalar_mul(z / qa, a_pt)));
+    let socle = ec.scalar_mul(qa / q, &c);
+    if socle.infinity || !ec.scalar_mul(qa, &c).infinity {
+        return None;
+    }
+    // el subgrupo de orden q de <c> no debe estar en <A>
+    let gamma = ec.scalar_mul(q.pow(b - 1), a_pt);
+    dlog_prime(ec, &socle, &gamma, q).is_none().then_some(c)
+}
+
+/// Intentos por primo antes de volver a muestrear P1.
+const COMPLEMENT_TRIES: usize = 16;
+
+/// Puntos que se prueban antes de concluir que `order` no es #E: con un
+/// multiplo erroneo del exponente todo punto cumple [order]P = O y, sin
+/// tope, se recorreria todo F_p.
+const STRUCTURE_TRIES: usize = 256;
+
+impl Curve {
+    /// Estructura de E(F_p) conocido su orden (ver el modulo).
+    pub fn group_structure(&self, order: u64) -> GroupStructure {
+        self.try_group_structure(order).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Como `group_structure`, con error `NoGenerator` si `order` no es #E
+    /// (tras `STRUCTURE_TRIES` puntos si es un multiplo del exponente).
+    pub fn try_group_structure(&self, order: u64) -> Result<GroupStructure> {
+        if order == 0 {
+            return Err(Error::NoGenerator { order });
+        }
+        let mut best = (Point::infinity(), 1u64);
+        let mut samples = curve_points(self).cycle();
+        for pt in curve_points(self).take(STRUCTURE_TRIES) {
+            if !self.scalar_mul(order, &pt).infinity {
+                return Err(Error::NoGenerator { order });
+            }
+            best = combine(self, best, (pt, order_from_multiple(self, &pt, order)));
+            let (p1, n2) = best;
+            let n1 = order / n2;
+            if !n2.is_multiple_of(n1) || !(self.p - 1).is_multiple_of(n1) {
+                continue;
+            }
+            // P2 = suma de los generadores complementarios de cada q-Sylow
+            let mut p2 = Some(Point::infinity());
+            for q in prime_factors(n1) {
+                let (e, b) = (valuation(order, q), valuation(n2, q));
+                let cofactor = order / q.pow(e);
+                let a_pt = self.scalar_mul(n2 / q.pow(b), &p1);
+                let found = samples.by_ref().take(COMPLEMENT_TRIES).find_map(|r| {
+                    sylow_complement(self, &self.scalar_mul(cofactor, &r), &a_pt, q, e - b, b)
+                });
+                p2 = p2.zip(found).map(|(acc, c)| self.point_add(&acc, &c));
+            }
+            if let Some(p2) = p2 {
+                return Ok(GroupStructure { n1, n2, p1, p2 });
+            }
+        }
+        Err(Error::NoGenerator { order })
+    }
+
+    /// Generador G del subgrupo de orden primo mas grande, con (G, q, h)
+    /// tales que #E = h * q. Error `NoGenerator` si `order` no es #E o si
+    /// ninguno de los primeros `STRUCTURE_TRIES` puntos tiene componente en el
+    /// q-Sylow.
+    pub fn prime_order_subgroup(&self, order: u64) -> Result<(Point, u64, u64)> {
+        let q = prime_factors(order).into_iter().max().ok_or(Error::NoGenerator { order })?;
+        let sylow = q.pow(valuation(order, q));
+        for pt in curve_points(self).take(STRUCTURE_TRIES) {
+            if !self.scalar_mul(order, &pt).infinity {
+                break;
+            }
+            // proyectar al q-Sylow y bajar a orden q (el q-Sylow puede no ser ciclico)
+            let s = self.scalar_mul(order / sylow, &pt);
+            if !s.infinity {
+                let g = self.scalar_mul(order_from_multiple(self, &s, sylow) / q, &s);
+                return Ok((g, q, order / q));
+            }
+        }
+        Err(Error::NoGenerator { order })
+    }
+}
+
+/// Estructura de y^2 = x^3 + b conocido su orden.
+pub fn group_structure(b: u64, p: u64, order: u64) -> GroupStructure {
+    Curve::new(0, b, p).group_structure(order)
+}
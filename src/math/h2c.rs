@@ -0,0 +1,198 @@
+/// Hash a curva (RFC 9380) sobre el backend u64.
+///
+/// `expand_message_xmd` (SHA-256) convierte (msg, DST) en bytes uniformes y
+/// `hash_to_field` los reduce a elementos de F_p con k = 128 bits de margen,
+/// de modo que el sesgo modulo p es despreciable. La version BigUint para
+/// secp256k1 (SSWU sobre la curva 3-isogena) esta en `math::big::h2c`.
+///
+/// Simplified SWU exige A != 0 y B != 0, asi que no sirve directamente para
+/// y^2 = x^3 + b. Shallue-van de Woestijne (seccion 6.6.1) funciona con
+/// cualquier curva de Weierstrass corta, incluidas las toy con j = 0:
+///
+/// - `map_to_curve_svdw`: u -> punto de E, con el signo de y ligado al de u.
+/// - `encode_to_curve_svdw`: un solo u (distribucion no uniforme).
+/// - `hash_to_curve_svdw`: map(u0) + map(u1), indistinguible de un oraculo
+///   aleatorio; ambos multiplican por el cofactor al final.
+
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+
+use crate::error::{Error, Result};
+use super::curve::Point;
+use super::field::*;
+use super::sha256::*;
+use super::weierstrass::Curve;
+
+/// Margen de seguridad k de hash_to_field (RFC 9380, seccion 5).
+pub const H2C_SECURITY_BITS: usize = 128;
+
+/// Prefijo para DST de mas de 255 bytes (seccion 5.3.3).
+const OVERSIZE_DST_PREFIX: &[u8] = b"H2C-OVERSIZE-DST-";
+
+/// DST' = DST || I2OSP(len(DST), 1), reduciendo con SHA-256 los DST largos.
+fn dst_prime(dst: &[u8]) -> Vec<u8> {
+    let mut out = if dst.len() > 255 {
+        let mut h = Sha256::new();
+        h.update(OVERSIZE_DST_PREFIX);
+        h.update(dst);
+        h.finalize().to_vec()
+    } else {
+        dst.to_vec()
+    };
+    out.push(out.len() as u8);
+    out
+}
+
+/// expand_message_xmd con SHA-256 (seccion 5.3.1): `len` bytes uniformes.
+/// Error `OutputTooLong` si len > 255 * 32.
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len: usize) -> Result<Vec<u8>> {
+    let ell = len.div_ceil(SHA256_OUTPUT);
+    if ell > 255 || len > u16::MAX as usize {
+        return Err(Error::OutputTooLong { len, max: 255 * SHA256_OUTPUT });
+    }
+    let dst = dst_prime(dst);
+
+    // b_0 = H(Z_pad || msg || I2OSP(len, 2) || 0 || DST')
+    let mut h = Sha256::new();
+    h.update(&[0u8; SHA256_BLOCK]);
+    h.update(msg);
+    h.update(&(len as u16).to_be_bytes());
+    h.update(&[0]);
+    h.update(&dst);
+    let b0 = h.finalize();
+
+    // b_1 = H(b_0 || 1 || DST'),  b_i = H((b_0 xor b_(i-1)) || i || DST')
+    let mut out = Vec::with_capacity(ell * SHA256_OUTPUT);
+    let mut prev = [0u8; SHA256_OUTPUT];
+    for i in 1..=ell {
+        let mut h = Sha256::new();
+        let mixed: Vec<u8> = b0.iter().zip(&prev).map(|(a, b)| a ^ b).collect();
+        h.update(&mixed);
+        h.update(&[i as u8]);
+        h.update(&dst);
+        prev = h.finalize();
+        out.extend_from_slice(&prev);
+    }
+    out.truncate(len);
+    Ok(out)
+}
+
+/// Bytes por elemento de F_p en hash_to_field: L = ceil((ceil(log2 p) + k) / 8).
+pub fn h2c_field_len(p_bits: usize) -> usize {
+    (p_bits + H2C_SECURITY_BITS).div_ceil(8)
+}
+
+/// `count` elementos de F_p a partir de (msg, DST) (seccion 5.2).
+pub fn hash_to_field(msg: &[u8], dst: &[u8], count: usize, p: u64) -> Vec<u64> {
+    let len = h2c_field_len(64 - p.leading_zeros() as usize);
+    let bytes = expand_message_xmd(msg, dst, count * len).expect("count * L <= 8160");
+    let modulus = BigUint::from(p);
+    bytes
+        .chunks(len)
+        .map(|chunk| (BigUint::from_bytes_be(chunk) % &modulus).to_u64().expect("< p"))
+        .collect()
+}
+
+/// sgn0 para F_p: paridad del representante canonico.
+fn sgn0(x: u64) -> u64 {
+    x & 1
+}
+
+/// 1/x, con 0 -> 0 (inv0 de la seccion 4).
+fn inv0(x: u64, p: u64) -> u64 {
+    if x == 0 { 0 } else { mod_inv(x, p) }
+}
+
+fn is_square(x: u64, p: u64) -> bool {
+    x == 0 || legendre(x, p) == 1
+}
+
+/// Constantes de SvdW para una curva (apendice F.1 / H.1).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SvdwParams {
+    pub z: u64,
+    c1: u64,
+    c2: u64,
+    c3: u64,
+    c4: u64,
+}
+
+impl SvdwParams {
+    /// Z con |Z| minimo (probando 1, -1, 2, -2, ...) tal que g(Z) != 0,
+    /// h(Z) = -(3Z^2 + 4A) / (4 g(Z)) es un cuadrado no nulo y g(Z) o
+    /// g(-Z/2) es cuadrado; de ahi c1 = g(Z), c2 = -Z/2,
+    /// c3 = sqrt(-g(Z)(3Z^2 + 4A)) con sgn0 = 0 y c4 = -4 g(Z) / (3Z^2 + 4A).
+    pub fn new(ec: &Curve) -> Self {
+        let p = ec.p;
+        let g = |x: u64| ec_rhs(ec, x);
+        let t = |z: u64| mod_add(mod_mul(3, mod_mul(z, z, p), p), mod_mul(4, ec.a, p), p);
+        let h = |z: u64| {
+            mod_mul(mod_sub(0, t(z), p), inv0(mod_mul(4, g(z), p), p), p)
+        };
+        let half = mod_inv(2, p);
+        let z = (1..p)
+            .flat_map(|c| [c, p - c])
+            .find(|&z| {
+                g(z) != 0
+                    && h(z) != 0
+                    && is_square(h(z), p)
+                    && (is_square(g(z), p) || is_square(g(mod_mul(mod_sub(0, z, p), half, p)), p))
+            })
+            .expect("toda curva sobre F_p con p > 3 tiene un Z valido");
+        let c1 = g(z);
+        let c2 = mod_mul(mod_sub(0, z, p), half, p);
+        let c3 = mod_sqrt(mod_mul(mod_sub(0, c1, p), t(z), p), p).expect("-g(Z) h(Z) es cuadrado");
+        let c3 = if sgn0(c3) == 1 { mod_sub(0, c3, p) } else { c3 };
+        let c4 = mod_mul(mod_sub(0, mod_mul(4, c1, p), p), mod_inv(t(z), p), p);
+        SvdwParams { z, c1, c2, c3, c4 }
+    }
+}
+
+/// x^3 + ax + b.
+fn ec_rhs(ec: &Curve, x: u64) -> u64 {
+    let p = ec.p;
+    mod_add(mod_add(mod_pow(x, 3, p), mod_mul(ec.a, x, p), p), ec.b, p)
+}
+
+/// Mapa de Shallue-van de Woestijne (seccion 6.6.1): u -> (x, y) en E.
+/// De los tres candidatos x1, x2, x3 al menos uno tiene g(x) cuadrado.
+pub fn map_to_curve_svdw(u: u64, ec: &Curve, params: &SvdwParams) -> Point {
+    let p = ec.p;
+    let SvdwParams { z, c1, c2, c3, c4 } = *params;
+    let u = u % p;
+    let tv1 = mod_mul(mod_mul(u, u, p), c1, p);
+    let tv2 = mod_add(1, tv1, p);
+    let tv1 = mod_sub(1, tv1, p);
+    let tv3 = inv0(mod_mul(tv1, tv2, p), p);
+    let tv4 = mod_mul(mod_mul(mod_mul(u, tv1, p), tv3, p), c3, p);
+
+    let x1 = mod_sub(c2, tv4, p);
+    let x2 = mod_add(c2, tv4, p);
+    let x3 = {
+        let t = mod_mul(mod_mul(tv2, tv2, p), tv3, p);
+        mod_add(mod_mul(mod_mul(t, t, p), c4, p), z, p)
+    };
+    let x = [x1, x2, x3]
+        .into_iter()
+        .find(|&x| is_square(ec_rhs(ec, x), p))
+        .expect("SvdW garantiza un candidato valido");
+    let y = mod_sqrt(ec_rhs(ec, x), p).expect("g(x) es cuadrado");
+    let y = if sgn0(u) == sgn0(y) { y } else { mod_sub(0, y, p) };
+    Point::new(x, y)
+}
+
+/// encode_to_curve con SvdW: clear_cofactor(map(u)), u = hash_to_field(msg, 1).
+pub fn encode_to_curve_svdw(ec: &Curve, cofactor: u64, msg: &[u8], dst: &[u8]) -> Point {
+    let params = SvdwParams::new(ec);
+    let u = hash_to_field(msg, dst, 1, ec.p);
+    ec.scalar_mul(cofactor, &map_to_curve_svdw(u[0], ec, &params))
+}
+
+/// hash_to_curve con SvdW: clear_cofactor(map(u0) + map(u1)).
+pub fn hash_to_curve_svdw(ec: &Curve, cofactor: u64, msg: &[u8], dst: &[u8]) -> Point {
+    let params = SvdwParams::new(ec);
+    let u = hash_to_field(msg, dst, 2, ec.p);
+    let q0 = map_to_curve_svdw(u[0], ec, &params);
+    let q1 = map_to_curve_svdw(u[1], ec, &params);
+    ec.scalar_mul(cofactor, &ec.point_add(&q0, &q1))
+}
@@ -0,0 +1,233 @@
+/// Coordenadas jacobianas (X : Y : Z) para y^2 = x^3 + ax + b, genericas sobre F.
+///
+/// (X, Y, Z) representa el punto afin (X/Z^2, Y/Z^3); Z = 0 es el infinito.
+/// Sumar y doblar no necesita inversiones: solo se invierte Z al volver a
+/// afin, y con `batch_normalize` una sola inversion sirve para un lote.
+/// Formulas de la Explicit-Formulas Database: dbl-2009-l para a = 0,
+/// dbl-2007-bl para a general, madd-2007-bl (suma mixta con un punto afin)
+/// y add-2007-bl. Las variantes `_a` reciben el coeficiente a de la curva.
+
+use super::fp::Field;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Jacobian<F: Field> {
+    pub x: F,
+    pub y: F,
+    pub z: F,
+}
+
+impl<F: Field> Jacobian<F> {
+    /// Punto en el infinito (1 : 1 : 0) del cuerpo de `like`.
+    pub fn identity(like: &F) -> Self {
+        Jacobian { x: like.one(), y: like.one(), z: like.zero() }
+    }
+
+    /// Punto afin (x, y) -> (x : y : 1). `like` fija el cuerpo para el infinito.
+    pub fn from_affine(pt: Option<(F, F)>, like: &F) -> Self {
+        match pt {
+            Some((x, y)) => Jacobian { x, y, z: x.one() },
+            None => Self::identity(like),
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    /// (X/Z^2, Y/Z^3), con una inversion.
+    pub fn to_affine(&self) -> Option<(F, F)> {
+        let z_inv = self.z.inv()?;
+        let z_inv2 = z_inv.square();
+        Some((self.x * z_inv2, self.y * z_inv2 * z_inv))
+    }
+
+    pub fn neg(&self) -> Self {
+        Jacobian { x: self.x, y: -self.y, z: self.z }
+    }
+
+    /// 2P con a = 0 (dbl-2009-l): 2M + 5S.
+    pub fn double(&self) -> Self {
+        if self.is_identity() || self.y.is_zero() {
+            return Self::identity(&self.x);
+        }
+        let a = self.x.square();
+        let b = self.y.square();
+        let c = b.square();
+        let d = ((self.x + b).square() - a - c).double();
+        let e = a.double() + a;
+        let x3 = e.square() - d.double();
+        let y3 = e * (d - x3) - c.double().double().double();
+        let z3 = (self.y * self.z).double();
+        Jacobian { x: x3, y: y3, z: z3 }
+    }
+
+    /// 2P con a general (dbl-2007-bl): 1M + 8S + 1 mult. por a.
+    pub fn double_a(&self, a: F) -> Self {
+        if self.is_identity() || self.y.is_zero() {
+            return Self::identity(&self.x);
+        }
+        let xx = self.x.square();
+        let yy = self.y.square();
+        let yyyy = yy.square();
+        let zz = self.z.square();
+        let s = ((self.x + yy).square() - xx - yyyy).double();
+        let m = xx.double() + xx + a * zz.square();
+        let t = m.square() - s.double();
+        let y3 = m * (s - t) - yyyy.double().double().double();
+        let z3 = (self.y + self.z).square() - yy - zz;
+        Jacobian { x: t, y: y3, z: z3 }
+    }
+
+    /// P + Q con Q afin (madd-2007-bl): 7M + 4S.
+    pub fn add_affine(&self, other: Option<(F, F)>) -> Self {
+        self.add_affine_with(other, Self::double)
+    }
+
+    /// Como `add_affine` en y^2 = x^3 + ax + b.
+    pub fn add_affine_a(&self, other: Option<(F, F)>, a: F) -> Self {
+        self.add_affine_with(other, |pt| pt.double_a(a))
+    }
+
+    /// P + Q con ambos jacobianos (add-2007-bl): 11M + 5S.
+    pub fn add(&self, other: &Self) -> Self {
+        self.add_with(other, Self::double)
+    }
+
+    /// Como `add` en y^2 = x^3 + ax + b.
+    pub fn add_a(&self, other: &Self, a: F) -> Self {
+        self.add_with(other, |pt| pt.double_a(a))
+    }
+
+    /// Suma mixta; `double` resuelve el caso P == Q.
+    fn add_affine_with(&self, other: Option<(F, F)>, double: impl FnOnce(&Self) -> Self) -> Self {
+        let (x2, y2) = match other {
+            Some(c) => c,
+            None => return *self,
+        };
+        if self.is_identity() {
+            return Self::from_affine(other, &x2);
+        }
+        let z1z1 = self.z.square();
+        let u2 = x2 * z1z1;
+        let s2 = y2 * self.z * z1z1;
+        let h = u2 - self.x;
+        let r = (s2 - self.y).double();
+        if h.is_zero() {
+            // misma abscisa: P == Q o P == -Q
+            return if r.is_zero() { double(self) } else { Self::identity(&x2) };
+        }
+        let hh = h.square();
+        let i = hh.double().double();
+        let j = h * i;
+        let v = self.x * i;
+        let x3 = r.square() - j - v.double();
+        let y3 = r * (v - x3) - (self.y * j).double();
+        let z3 = (self.z + h).square() - z1z1 - hh;
+        Jacobian { x: x3, y: y3, z: z3 }
+    }
+
+    /// Suma jacobiana completa; `double` resuelve el caso P == Q.
+    fn add_with(&self, other: &Self, double: impl FnOnce(&Self) -> Self) -> Self {
+        if self.is_identity() {
+            return *other;
+        }
+        if other.is_identity() {
+            return *self;
+        }
+        let z1z1 = self.z.square();
+        let z2z2 = other.z.square();
+        let u1 = self.x * z2z2;
+        let u2 = other.x * z1z1;
+        let s1 = self.y * other.z * z2z2;
+        let s2 = other.y * self.z * z1z1;
+        let h = u2 - u1;
+        let r = (s2 - s1).double();
+        if h.is_zero() {
+            return if r.is_zero() { double(self) } else { Self::identity(&self.x) };
+        }
+        let i = h.double().square();
+        let j = h * i;
+        let v = u1 * i;
+        let x3 = r.square() - j - v.double();
+        let y3 = r * (v - x3) - (s1 * j).double();
+        let z3 = ((self.z + other.z).square() - z1z1 - z2z2) * h;
+        Jacobian { x: x3, y: y3, z: z3 }
+    }
+}
+
+/// Igualdad proyectiva: X1 Z2^2 = X2 Z1^2 y Y1 Z2^3 = Y2 Z1^3.
+impl<F: Field> PartialEq for Jacobian<F> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.is_identity(), other.is_identity()) {
+            (true, true) => true,
+            (false, false) => {
+                let z1z1 = self.z.square();
+                let z2z2 = other.z.square();
+                self.x * z2z2 == other.x * z1z1
+                    && self.y * z2z2 * other.z == other.y * z1z1 * self.z
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<F: Field> Eq for Jacobian<F> {}
+
+/// k*P con doblados jacobianos y sumas mixtas, desde el bit mas alto.
+/// `k` en limbs u64 little-endian; una sola inversion al final.
+pub fn jacobian_mul<F: Field>(k: &[u64], pt: Option<(F, F)>) -> Option<(F, F)> {
+    let (x, _) = pt?;
+    let mut acc = Jacobian::identity(&x);
+    for i in (0..k.len() * 64).rev() {
+        acc = acc.double();
+        if (k[i / 64] >> (i % 64)) & 1 == 1 {
+            acc = acc.add_affine(pt);
+        }
+    }
+    acc.to_affine()
+}
+
+/// Como `jacobian_mul` en y^2 = x^3 + ax + b.
+pub fn jacobian_mul_a<F: Field>(k: &[u64], pt: Option<(F, F)>, a: F) -> Option<(F, F)> {
+    let (x, _) = pt?;
+    let mut acc = Jacobian::identity(&x);
+    for i in (0..k.len() * 64).rev() {
+        acc = acc.double_a(a);
+        if (k[i / 64] >> (i % 64)) & 1 == 1 {
+            acc = acc.add_affine_a(pt, a);
+        }
+    }
+    acc.to_affine()
+}
+
+/// Normaliza un lote a afin con una sola inversion (truco de Montgomery
+/// sobre las Z no nulas). Los puntos en el infinito quedan como None.
+pub fn batch_normalize<F: Field>(pts: &[Jacobian<F>]) -> Vec<Option<(F, F)>> {
+    let first = match pts.first() {
+        Some(pt) => pt.x,
+        None => return Vec::new(),
+    };
+
+    // prefix[i] = producto de las Z no nulas anteriores a i
+    let mut prefix = Vec::with_capacity(pts.len());
+    let mut acc = first.one();
+    for pt in pts {
+        prefix.push(acc);
+        if !pt.is_identity() {
+            acc = acc * pt.z;
+        }
+    }
+
+    let mut inv = acc.inv().expect("producto de Z no nulas");
+    let mut out = vec![None; pts.len()];
+    for (i, pt) in pts.iter().enumerate().rev() {
+        if pt.is_identity() {
+            continue;
+        }
+        let z_inv = inv * prefix[i];
+        inv = inv * pt.z;
+        let z_inv2 = z_inv.square();
+        out[i] = Some((pt.x * z_inv2, pt.y * z_inv2 * z_inv));
+    }
+    out
+}
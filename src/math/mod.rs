@@ -1,8 +1,48 @@
 /// Fundamentos matemáticos: aritmética modular y operaciones de curva elíptica.
 
 pub mod field;
+pub mod fp;
+pub mod roots;
+pub mod primes;
+pub mod fp2;
+pub mod ext;
+pub mod jacobian;
+pub mod wnaf;
+pub mod msm;
 pub mod curve;
+pub mod weierstrass;
+pub mod sec1;
+pub mod poly;
+pub mod division;
+pub mod torsion;
+pub mod schoof;
+pub mod twist;
+pub mod group;
+pub mod rng;
+pub mod sha256;
+pub mod h2c;
+pub mod big;
+pub mod secp256k1;
 
 // Re-exportar todo para acceso directo: d3_symmetry::math::*
 pub use field::*;
+pub use fp::*;
+pub use roots::*;
+pub use primes::*;
+pub use fp2::*;
+pub use ext::*;
+pub use jacobian::*;
+pub use wnaf::*;
+pub use msm::*;
 pub use curve::*;
+pub use weierstrass::*;
+pub use sec1::*;
+pub use poly::*;
+pub use division::*;
+pub use torsion::*;
+pub use schoof::*;
+pub use twist::*;
+pub use group::*;
+pub use rng::*;
+pub use sha256::*;
+pub use h2c::*;
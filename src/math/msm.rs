@@ -0,0 +1,126 @@
+/// Multiplicacion multi-escalar sum_i k_i * P_i en y^2 = x^3 + b.
+///
+/// - Straus (wNAF intercalado): una sola cadena de doblados compartida y
+///   una tabla de multiplos impares por punto, todas normalizadas con una
+///   unica inversion. Es lo mejor para pocos puntos.
+/// - Pippenger (buckets): por cada ventana de c bits reparte los puntos en
+///   2^c - 1 cubetas segun su digito y las combina con sumas acumuladas.
+///   El coste por punto baja con el tamano del lote, asi que gana en lotes
+///   grandes.
+///
+/// Los escalares van en limbs u64 little-endian (`AsRef<[u64]>`), como en
+/// `affine_mul`; los puntos en el infinito y los escalares nulos no aportan.
+
+use super::fp::Field;
+use super::jacobian::{batch_normalize, Jacobian};
+use super::wnaf::{odd_multiples_jacobian, wnaf};
+
+/// Por debajo de este numero de puntos `msm` usa Straus; desde aqui, Pippenger
+/// (cruce medido en `benches/msm.rs` con escalares de 256 bits).
+pub const PIPPENGER_THRESHOLD: usize = 128;
+
+/// Ventana wNAF de Straus: tablas de 2^(w-2) = 4 puntos.
+const STRAUS_WINDOW: u32 = 4;
+
+fn bit_length(k: &[u64]) -> usize {
+    k.iter().rposition(|&l| l != 0).map_or(0, |i| i * 64 + 64 - k[i].leading_zeros() as usize)
+}
+
+/// Primer punto finito, para fijar el cuerpo del infinito jacobiano.
+fn field_of<F: Field>(points: &[Option<(F, F)>]) -> Option<F> {
+    points.iter().find_map(|pt| pt.map(|(x, _)| x))
+}
+
+/// sum_i k_i * P_i, eligiendo Straus o Pippenger segun el tamano del lote.
+pub fn msm<F: Field, K: AsRef<[u64]>>(scalars: &[K], points: &[Option<(F, F)>]) -> Option<(F, F)> {
+    if points.len() < PIPPENGER_THRESHOLD {
+        msm_straus(scalars, points)
+    } else {
+        msm_pippenger(scalars, points)
+    }
+}
+
+/// Straus con wNAF intercalado.
+pub fn msm_straus<F: Field, K: AsRef<[u64]>>(scalars: &[K], points: &[Option<(F, F)>]) -> Option<(F, F)> {
+    assert_eq!(scalars.len(), points.len(), "msm: escalares y puntos no coinciden");
+    let like = field_of(points)?;
+    let zero = like.zero();
+    let count = 1usize << (STRAUS_WINDOW - 2);
+
+    let mut digits = Vec::new();
+    let mut tables = Vec::new();
+    for (k, pt) in scalars.iter().zip(points) {
+        let (Some(pt), d) = (pt, wnaf(k.as_ref(), STRAUS_WINDOW)) else { continue };
+        if d.is_empty() {
+            continue;
+        }
+        digits.push(d);
+        tables.extend(odd_multiples_jacobian(*pt, count, zero));
+    }
+    let tables = batch_normalize(&tables);
+
+    let len = digits.iter().map(Vec::len).max().unwrap_or(0);
+    let mut acc = Jacobian::identity(&like);
+    for bit in (0..len).rev() {
+        acc = acc.double();
+        for (i, d) in digits.iter().enumerate() {
+            let d = d.get(bit).copied().unwrap_or(0);
+            if d > 0 {
+                acc = acc.add_affine(tables[i * count + (d / 2) as usize]);
+            } else if d < 0 {
+                let neg = tables[i * count + (-d / 2) as usize].map(|(x, y)| (x, -y));
+                acc = acc.add_affine(neg);
+            }
+        }
+    }
+    acc.to_affine()
+}
+
+/// Ancho de ventana de Pippenger para n puntos y escalares de `bits` bits:
+/// minimiza ceil(bits/c) * (n + 2^(c+1)), es decir, las n sumas de reparto
+/// mas las ~2^(c+1) de combinar cubetas en cada una de las ventanas.
+pub fn pippenger_window(n: usize, bits: usize) -> usize {
+    (2..=16usize)
+        .min_by_key(|&c| bits.div_ceil(c) * (n + (1 << (c + 1))))
+        .expect("rango no vacio")
+}
+
+/// Pippenger con cubetas: sum_i k_i P_i = sum_j 2^(c*j) sum_d d * B_{j,d}.
+pub fn msm_pippenger<F: Field, K: AsRef<[u64]>>(scalars: &[K], points: &[Option<(F, F)>]) -> Option<(F, F)> {
+    assert_eq!(scalars.len(), points.len(), "msm: escalares y puntos no coinciden");
+    let like = field_of(points)?;
+    let identity = Jacobian::identity(&like);
+    let bits = scalars.iter().map(|k| bit_length(k.as_ref())).max().unwrap_or(0);
+    let c = pippenger_window(points.len(), bits);
+    let windows = bits.div_ceil(c);
+
+    let digit = |k: &[u64], pos: usize| -> usize {
+        (0..c).fold(0, |d, i| {
+            let bit = pos + i;
+            let set = k.get(bit / 64).is_some_and(|&l| (l >> (bit % 64)) & 1 == 1);
+            d | (usize::from(set) << i)
+        })
+    };
+
+    let mut acc = identity;
+    for window in (0..windows).rev() {
+        for _ in 0..c {
+            acc = acc.double();
+        }
+        let mut buckets = vec![identity; (1 << c) - 1];
+        for (k, pt) in scalars.iter().zip(points) {
+            let d = digit(k.as_ref(), window * c);
+            if d != 0 {
+                buckets[d - 1] = buckets[d - 1].add_affine(*pt);
+            }
+        }
+        // sum_d d * B_d con sumas acumuladas desde la cubeta mas alta
+        let (mut running, mut total) = (identity, identity);
+        for bucket in buckets.iter().rev() {
+            running = running.add(bucket);
+            total = total.add(&running);
+        }
+        acc = acc.add(&total);
+    }
+    acc.to_affine()
+}
@@ -0,0 +1,322 @@
+/// Polinomios univariados sobre F_p con coeficientes u64.
+///
+/// Base de los polinomios de division, de Schoof y de las extensiones
+/// F_{p^k} (`Fpk`): producto, resto y exponenciacion modular en
+/// F_p[x]/(h), mcd, mcd extendido y raices en F_p (Cantor-Zassenhaus). Los
+/// coeficientes se guardan de menor a mayor grado y sin ceros finales, asi
+/// que el polinomio nulo es el vector vacio.
+///
+/// El producto y el resto acumulan en u128 y reducen mod p una sola vez por
+/// coeficiente (salvo desbordamiento), en lugar de una division u128 por
+/// cada producto de coeficientes.
+
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use super::field::*;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Poly {
+    coeffs: Vec<u64>,
+    p: u64,
+}
+
+/// Suma `prod` al acumulador, reduciendolo antes si desbordaria.
+#[inline]
+fn accumulate(slot: &mut u128, prod: u128, p: u64) {
+    *slot = match slot.checked_add(prod) {
+        Some(v) => v,
+        None => *slot % p as u128 + prod,
+    };
+}
+
+impl Poly {
+    /// Polinomio sum_i coeffs[i] x^i con coeficientes reducidos mod p.
+    pub fn new(coeffs: Vec<u64>, p: u64) -> Self {
+        let coeffs = coeffs.into_iter().map(|c| c % p).collect();
+        Poly::from_reduced(coeffs, p)
+    }
+
+    fn from_reduced(mut coeffs: Vec<u64>, p: u64) -> Self {
+        while coeffs.last() == Some(&0) {
+            coeffs.pop();
+        }
+        Poly { coeffs, p }
+    }
+
+    pub fn zero(p: u64) -> Self {
+        Poly { coeffs: Vec::new(), p }
+    }
+
+    pub fn constant(c: u64, p: u64) -> Self {
+        Poly::new(vec![c], p)
+    }
+
+    pub fn one(p: u64) -> Self {
+        Poly::constant(1, p)
+    }
+
+    /// El polinomio x.
+    pub fn x(p: u64) -> Self {
+        Poly::new(vec![0, 1], p)
+    }
+
+    pub fn modulus(&self) -> u64 {
+        self.p
+    }
+
+    /// Coeficientes de menor a mayor grado.
+    pub fn coeffs(&self) -> &[u64] {
+        &self.coeffs
+    }
+
+    /// Grado; None para el polinomio nulo.
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// Coeficiente principal (0 para el polinomio nulo).
+    pub fn leading(&self) -> u64 {
+        self.coeffs.last().copied().unwrap_or(0)
+    }
+
+    /// Evaluacion por Horner.
+    pub fn eval(&self, x: u64) -> u64 {
+        self.coeffs.iter().rev().fold(0, |acc, &c| mod_add(mod_mul(acc, x, self.p), c, self.p))
+    }
+
+    pub fn scale(&self, c: u64) -> Poly {
+        let coeffs = self.coeffs.iter().map(|&a| mod_mul(a, c, self.p)).collect();
+        Poly::from_reduced(coeffs, self.p)
+    }
+
+    /// Divide por el coeficiente principal. El polinomio nulo queda igual.
+    pub fn monic(&self) -> Poly {
+        match self.leading() {
+            0 | 1 => self.clone(),
+            lead => self.scale(mod_inv(lead, self.p)),
+        }
+    }
+
+    /// Cociente y resto de la division euclidea. Panics si `d` es nulo.
+    pub fn div_rem(&self, d: &Poly) -> (Poly, Poly) {
+        let p = self.p;
+        let dd = d.degree().expect("division por el polinomio nulo");
+        if self.coeffs.len() <= dd {
+            return (Poly::zero(p), self.clone());
+        }
+        let inv = mod_inv(d.leading(), p);
+        let mut rem = self.coeffs.clone();
+        let mut quot = vec![0u64; rem.len() - dd];
+        for i in (dd..rem.len()).rev() {
+            let c = mod_mul(rem[i], inv, p);
+            if c == 0 {
+                continue;
+            }
+            quot[i - dd] = c;
+            for (j, &dj) in d.coeffs.iter().enumerate() {
+                rem[i - dd + j] = mod_sub(rem[i - dd + j], mod_mul(c, dj, p), p);
+            }
+        }
+        rem.truncate(dd);
+        (Poly::from_reduced(quot, p), Poly::from_reduced(rem, p))
+    }
+
+    /// Resto modulo `m`, acumulando en u128. Panics si `m` es nulo.
+    pub fn rem(&self, m: &Poly) -> Poly {
+        let p = self.p;
+        let d = m.degree().expect("division por el polinomio nulo");
+        if self.coeffs.len() <= d {
+            return self.clone();
+        }
+        // x^d ≡ sum_j neg[j] x^j (mod m)
+        let inv = mod_inv(m.leading(), p);
+        let neg: Vec<u64> = m.coeffs[..d].iter().map(|&c| mod_mul(mod_sub(0, c, p), inv, p)).collect();
+        let mut acc: Vec<u128> = self.coeffs.iter().map(|&c| c as u128).collect();
+        for i in (d..acc.len()).rev() {
+            let c = (acc[i] % p as u128) as u64;
+            if c == 0 {
+                continue;
+            }
+            for (j, &nj) in neg.iter().enumerate() {
+                accumulate(&mut acc[i - d + j], c as u128 * nj as u128, p);
+            }
+        }
+        let coeffs = acc[..d].iter().map(|&v| (v % p as u128) as u64).collect();
+        Poly::from_reduced(coeffs, p)
+    }
+
+    /// self * other mod m.
+    pub fn mul_mod(&self, other: &Poly, m: &Poly) -> Poly {
+        (self * other).rem(m)
+    }
+
+    /// self^exp mod m por square-and-multiply.
+    pub fn pow_mod(&self, mut exp: u64, m: &Poly) -> Poly {
+        let mut result = Poly::one(self.p).rem(m);
+        let mut base = self.rem(m);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.mul_mod(&base, m);
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.mul_mod(&base, m);
+            }
+        }
+        result
+    }
+
+    /// mcd monico (0 si ambos son nulos).
+    pub fn gcd(&self, other: &Poly) -> Poly {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let r = a.rem(&b);
+            a = b;
+            b = r;
+        }
+        a.monic()
+    }
+
+    /// mcd extendido respecto a `m`: (g, s) con g = mcd(self, m) monico y
+    /// s * self ≡ g (mod m).
+    pub fn gcd_ext(&self, m: &Poly) -> (Poly, Poly) {
+        let p = self.p;
+        let (mut old_r, mut r) = (self.rem(m), m.clone());
+        let (mut old_s, mut s) = (Poly::one(p), Poly::zero(p));
+        while !r.is_zero() {
+            let (q, rem) = old_r.div_rem(&r);
+            old_r = std::mem::replace(&mut r, rem);
+            let next = &old_s - &(&q * &s);
+            old_s = std::mem::replace(&mut s, next);
+        }
+        match old_r.leading() {
+            0 => (old_r, Poly::zero(p)),
+            lead => {
+                let inv = mod_inv(lead, p);
+                (old_r.scale(inv), old_s.scale(inv).rem(m))
+            }
+        }
+    }
+
+    /// Inverso modulo `m`; None si mcd(self, m) != 1.
+    pub fn inv_mod(&self, m: &Poly) -> Option<Poly> {
+        let (g, s) = self.gcd_ext(m);
+        (g.degree() == Some(0)).then_some(s)
+    }
+
+    /// Raices distintas en F_p, en orden creciente. Panics si es nulo.
+    ///
+    /// mcd(self, x^p - x) es el producto de los factores lineales; se parte
+    /// con mcd(g, (x + d)^((p-1)/2) - 1) para d = 0, 1, 2, ..., que separa
+    /// las raices r segun r + d sea residuo cuadratico o no (Cantor-Zassenhaus
+    /// de grado 1, con d determinista en lugar de aleatorio).
+    pub fn roots(&self) -> Vec<u64> {
+        assert!(!self.is_zero(), "el polinomio nulo se anula en todo F_p");
+        let p = self.p;
+        if self.degree() == Some(0) {
+            return Vec::new();
+        }
+        if p == 2 {
+            return (0..2).filter(|&x| self.eval(x) == 0).collect();
+        }
+        let x = Poly::x(p);
+        let linear = (&x.pow_mod(p, self) - &x).gcd(self);
+        let mut roots = Vec::new();
+        let mut pending = vec![linear];
+        while let Some(g) = pending.pop() {
+            match g.degree() {
+                None | Some(0) => {}
+                Some(1) => roots.push(mod_mul(mod_sub(0, g.coeffs[0], p), mod_inv(g.coeffs[1], p), p)),
+                Some(deg) => {
+                    let h = (0..p)
+                        .map(|d| {
+                            let shifted = Poly::new(vec![d, 1], p);
+                            (&shifted.pow_mod((p - 1) / 2, &g) - &Poly::one(p)).gcd(&g)
+                        })
+                        .find(|h| matches!(h.degree(), Some(k) if k > 0 && k < deg))
+                        .expect("algun desplazamiento separa dos raices distintas");
+                    pending.push(g.div_rem(&h).0);
+                    pending.push(h);
+                }
+            }
+        }
+        roots.sort_unstable();
+        roots
+    }
+}
+
+impl Add for &Poly {
+    type Output = Poly;
+    fn add(self, other: &Poly) -> Poly {
+        let (long, short) = if self.coeffs.len() >= other.coeffs.len() { (self, other) } else { (other, self) };
+        let mut coeffs = long.coeffs.clone();
+        for (c, &s) in coeffs.iter_mut().zip(&short.coeffs) {
+            *c = mod_add(*c, s, self.p);
+        }
+        Poly::from_reduced(coeffs, self.p)
+    }
+}
+
+impl Sub for &Poly {
+    type Output = Poly;
+    fn sub(self, other: &Poly) -> Poly {
+        self + &(-other)
+    }
+}
+
+impl Neg for &Poly {
+    type Output = Poly;
+    fn neg(self) -> Poly {
+        let coeffs = self.coeffs.iter().map(|&c| mod_sub(0, c, self.p)).collect();
+        Poly::from_reduced(coeffs, self.p)
+    }
+}
+
+impl Mul for &Poly {
+    type Output = Poly;
+    fn mul(self, other: &Poly) -> Poly {
+        let p = self.p;
+        if self.is_zero() || other.is_zero() {
+            return Poly::zero(p);
+        }
+        let mut acc = vec![0u128; self.coeffs.len() + other.coeffs.len() - 1];
+        for (i, &a) in self.coeffs.iter().enumerate() {
+            if a == 0 {
+                continue;
+            }
+            for (j, &b) in other.coeffs.iter().enumerate() {
+                accumulate(&mut acc[i + j], a as u128 * b as u128, p);
+            }
+        }
+        let coeffs = acc.iter().map(|&v| (v % p as u128) as u64).collect();
+        Poly::from_reduced(coeffs, p)
+    }
+}
+
+impl fmt::Display for Poly {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let terms: Vec<String> = self
+            .coeffs
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|&(_, &c)| c != 0)
+            .map(|(i, &c)| match (i, c) {
+                (0, c) => format!("{}", c),
+                (1, 1) => "x".to_string(),
+                (1, c) => format!("{}x", c),
+                (i, 1) => format!("x^{}", i),
+                (i, c) => format!("{}x^{}", c, i),
+            })
+            .collect();
+        write!(f, "{}", terms.join(" + "))
+    }
+}
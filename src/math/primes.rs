@@ -0,0 +1,181 @@
+/// Primalidad y factorizacion de enteros para ordenes de grupo.
+///
+/// Miller-Rabin es determinista en u64 (las 12 primeras bases primas bastan
+/// para todo n < 3.3 * 10^24) y probabilistico sobre BigUint. La
+/// factorizacion combina division por primos pequenos con Pollard-rho en
+/// la variante de Brent.
+
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+use super::field::*;
+
+/// Bases de Miller-Rabin deterministas para n < 2^64.
+const MR_BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Primos pequenos para la division previa a Pollard-rho.
+const SMALL_PRIMES: [u64; 25] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83,
+    89, 97,
+];
+
+/// Una ronda de Miller-Rabin con base `a` sobre n - 1 = 2^s * d.
+fn miller_rabin_round(n: u64, a: u64, d: u64, s: u32) -> bool {
+    let mut x = mod_pow(a % n, d, n);
+    if x == 1 || x == n - 1 {
+        return true;
+    }
+    for _ in 1..s {
+        x = mod_mul(x, x, n);
+        if x == n - 1 {
+            return true;
+        }
+    }
+    false
+}
+
+/// Primalidad determinista para u64 (Miller-Rabin con bases fijas).
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for &q in SMALL_PRIMES.iter() {
+        if n.is_multiple_of(q) {
+            return n == q;
+        }
+    }
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    MR_BASES.iter().all(|&a| miller_rabin_round(n, a, d, s))
+}
+
+/// Primalidad probabilistica para BigUint: `rounds` rondas de Miller-Rabin
+/// con las primeras bases primas. Un compuesto pasa con probabilidad
+/// menor que 4^(-rounds) salvo que se haya construido contra esas bases.
+pub fn is_probable_prime_big(n: &BigUint, rounds: usize) -> bool {
+    let two = BigUint::from(2u32);
+    if *n < two {
+        return false;
+    }
+    for &q in SMALL_PRIMES.iter() {
+        let q = BigUint::from(q);
+        if (n % &q).is_zero() {
+            return *n == q;
+        }
+    }
+    let n1 = n - 1u32;
+    let s = n1.trailing_zeros().unwrap_or(0);
+    let d = &n1 >> s;
+
+    let mut bases = Vec::with_capacity(rounds);
+    let mut c = 2u64;
+    while bases.len() < rounds {
+        if is_prime(c) {
+            bases.push(BigUint::from(c));
+        }
+        c += 1;
+    }
+
+    bases.iter().all(|a| {
+        // n primo menor que la ultima base: a = n no es testigo
+        if (a % n).is_zero() {
+            return true;
+        }
+        let mut x = a.modpow(&d, n);
+        if x.is_one() || x == n1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = x.modpow(&two, n);
+            if x == n1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// Un divisor no trivial de n (compuesto e impar) por Pollard-rho/Brent:
+/// f(x) = x^2 + c, con productos acumulados para ahorrar mcd.
+fn pollard_brent(n: u64) -> u64 {
+    const BATCH: u64 = 128;
+    for c in 1..n {
+        let f = |x: u64| mod_add(mod_mul(x, x, n), c, n);
+        let (mut y, mut r, mut q, mut g) = (2u64, 1u64, 1u64, 1u64);
+        let (mut x, mut ys) = (y, y);
+        while g == 1 {
+            x = y;
+            for _ in 0..r {
+                y = f(y);
+            }
+            let mut k = 0;
+            while k < r && g == 1 {
+                ys = y;
+                for _ in 0..BATCH.min(r - k) {
+                    y = f(y);
+                    q = mod_mul(q, x.abs_diff(y), n);
+                }
+                g = q.gcd(&n);
+                k += BATCH;
+            }
+            r *= 2;
+        }
+        if g == n {
+            // el producto acumulado colapso: repetir paso a paso desde ys
+            loop {
+                ys = f(ys);
+                g = x.abs_diff(ys).gcd(&n);
+                if g > 1 {
+                    break;
+                }
+            }
+        }
+        if g != n {
+            return g;
+        }
+    }
+    unreachable!("n compuesto siempre tiene un divisor alcanzable")
+}
+
+fn factor_into(n: u64, out: &mut Vec<u64>) {
+    if n == 1 {
+        return;
+    }
+    if is_prime(n) {
+        out.push(n);
+        return;
+    }
+    let d = pollard_brent(n);
+    factor_into(d, out);
+    factor_into(n / d, out);
+}
+
+/// Factorizacion completa de n como pares (primo, exponente) ordenados.
+/// factor(1) = [] y factor(0) no esta definido.
+pub fn factor(mut n: u64) -> Vec<(u64, u32)> {
+    assert!(n > 0, "factor(0) no esta definido");
+    let mut primes = Vec::new();
+    for &q in SMALL_PRIMES.iter() {
+        while n.is_multiple_of(q) {
+            primes.push(q);
+            n /= q;
+        }
+    }
+    factor_into(n, &mut primes);
+    primes.sort_unstable();
+
+    let mut result: Vec<(u64, u32)> = Vec::new();
+    for q in primes {
+        match result.last_mut() {
+            Some((last, e)) if *last == q => *e += 1,
+            _ => result.push((q, 1)),
+        }
+    }
+    result
+}
+
+/// Divisores primos distintos de n, ordenados.
+pub fn prime_factors(n: u64) -> Vec<u64> {
+    factor(n).into_iter().map(|(q, _)| q).collect()
+}
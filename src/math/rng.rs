@@ -0,0 +1,125 @@
+/// Muestreo aleatorio reproducible: escalares, puntos y generadores.
+///
+/// Todos los experimentos parten de una semilla `u64`: la misma semilla da
+/// la misma secuencia de claves, puntos y caminos de Rho, y semillas
+/// distintas dan instancias independientes para promediar. El generador es
+/// splitmix64 (estado de 64 bits, periodo 2^64), suficiente para
+/// estadistica; no es un CSPRNG y no sirve para generar claves reales.
+///
+/// - `Rng::below` / `Rng::scalar`: enteros uniformes en [0, n) / [1, n),
+///   por rechazo para evitar el sesgo de `% n`.
+/// - `Curve::random_point`: x uniforme hasta que x^3 + ax + b es cuadrado,
+///   y signo de y aleatorio.
+/// - `Curve::random_generator` / `random_prime_order_generator`: punto
+///   aleatorio llevado al subgrupo (por el cofactor o proyectando al
+///   q-Sylow) hasta que no sea O.
+
+use crate::error::{Error, Result};
+use super::curve::Point;
+use super::field::*;
+use super::group::order_from_multiple;
+use super::primes::*;
+use super::weierstrass::Curve;
+
+/// Intentos antes de concluir que no hay puntos del orden pedido.
+const GENERATOR_TRIES: usize = 256;
+
+/// Generador splitmix64 con semilla.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniforme en [0, n). Rechaza la cola [zona, 2^64) con zona multiplo de n.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "intervalo vacio");
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let r = self.next_u64();
+            if r < zone {
+                return r % n;
+            }
+        }
+    }
+
+    /// Escalar uniforme en [1, n): clave secreta o coeficiente no nulo mod n.
+    pub fn scalar(&mut self, n: u64) -> u64 {
+        assert!(n > 1, "no hay escalares no nulos mod {}", n);
+        1 + self.below(n - 1)
+    }
+
+    pub fn bit(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    /// Semilla derivada para un subexperimento (ensayo i de una serie).
+    pub fn fork(&mut self) -> Rng {
+        Rng::new(self.next_u64())
+    }
+}
+
+impl Curve {
+    /// Punto finito con x uniforme entre las abscisas de la curva y signo de
+    /// y aleatorio. Cada punto con y != 0 tiene la misma probabilidad.
+    pub fn random_point(&self, rng: &mut Rng) -> Point {
+        let p = self.p;
+        loop {
+            let x = rng.below(p);
+            let rhs = mod_add(mod_add(mod_pow(x, 3, p), mod_mul(self.a, x, p), p), self.b, p);
+            if let Some(y) = mod_sqrt(rhs, p) {
+                let y = if rng.bit() { mod_sub(0, y, p) } else { y };
+                return Point::new(x, y);
+            }
+        }
+    }
+
+    /// Generador aleatorio del subgrupo de orden `n` (primo), con `#E = h * n`:
+    /// [h]R para R aleatorio, descartando O. Error `NoGenerator` si tras
+    /// `GENERATOR_TRIES` intentos no aparece ninguno (n ∤ #E / h).
+    pub fn random_generator(&self, n: u64, cofactor: u64, rng: &mut Rng) -> Result<Point> {
+        for _ in 0..GENERATOR_TRIES {
+            let g = self.scalar_mul(cofactor, &self.random_point(rng));
+            if !g.infinity && self.scalar_mul(n, &g).infinity {
+                return Ok(g);
+            }
+        }
+        Err(Error::NoGenerator { order: n })
+    }
+
+    /// Generador aleatorio del subgrupo de orden primo mas grande, con
+    /// (G, q, h) tales que #E = h * q (version aleatoria de
+    /// `prime_order_subgroup`). Proyecta R al q-Sylow y baja a orden q, que
+    /// sirve tambien si q^2 | #E. Error `NoGenerator` si `order` no es #E.
+    pub fn random_prime_order_generator(&self, order: u64, rng: &mut Rng) -> Result<(Point, u64, u64)> {
+        let q = prime_factors(order).into_iter().max().ok_or(Error::NoGenerator { order })?;
+        let mut sylow = q;
+        while (order / sylow).is_multiple_of(q) {
+            sylow *= q;
+        }
+        for _ in 0..GENERATOR_TRIES {
+            let pt = self.random_point(rng);
+            if !self.scalar_mul(order, &pt).infinity {
+                break;
+            }
+            let s = self.scalar_mul(order / sylow, &pt);
+            if !s.infinity {
+                let g = self.scalar_mul(order_from_multiple(self, &s, sylow) / q, &s);
+                return Ok((g, q, order / q));
+            }
+        }
+        Err(Error::NoGenerator { order })
+    }
+}
@@ -0,0 +1,144 @@
+/// Extraccion de raices en F_p: raices r-esimas (Adleman-Manders-Miller),
+/// raices cubicas y caracter cubico.
+///
+/// En una curva j=0 las raices cubicas aparecen por todas partes: recuperar
+/// x a partir de y en y^2 = x^3 + b, clasificar twists segun si b es cubo,
+/// u obtener beta directamente. Todas las funciones devuelven `Option`.
+
+use super::field::*;
+use super::primes::is_prime;
+
+/// Inverso de a modulo m (m no necesariamente primo). None si gcd(a, m) != 1.
+fn inv_mod(a: u64, m: u64) -> Option<u64> {
+    try_mod_inv(a, m).ok()
+}
+
+/// Raiz r-esima de `a` en F_p, con r primo. None si r no es primo o `a` no
+/// es potencia r-esima.
+///
+/// Si r no divide a p-1 la raiz es unica: a^(r^(-1) mod (p-1)). Si r | p-1
+/// se usa Adleman-Manders-Miller, que generaliza Tonelli-Shanks: se escribe
+/// p-1 = r^t * s y se corrige la raiz candidata dentro del r-subgrupo de
+/// Sylow resolviendo logaritmos discretos en un grupo de orden r.
+pub fn mod_rth_root(a: u64, r: u64, p: u64) -> Option<u64> {
+    if !is_prime(r) {
+        return None;
+    }
+    let a = a % p;
+    if a == 0 || p == 2 {
+        return Some(a);
+    }
+
+    // r no divide a p-1: x -> x^r es una biyeccion de F_p*
+    if !(p - 1).is_multiple_of(r) {
+        let e = inv_mod(r, p - 1)?;
+        return Some(mod_pow(a, e, p));
+    }
+
+    // Criterio de Euler generalizado: a es potencia r-esima sii a^((p-1)/r) = 1
+    if mod_pow(a, (p - 1) / r, p) != 1 {
+        return None;
+    }
+
+    // p-1 = r^t * s con gcd(r, s) = 1
+    let mut s = p - 1;
+    let mut t = 0u32;
+    while s.is_multiple_of(r) {
+        s /= r;
+        t += 1;
+    }
+
+    // rho: un no-residuo r-esimo
+    let rho = (2..p).find(|&z| mod_pow(z, (p - 1) / r, p) != 1)?;
+
+    // alpha con r*alpha ≡ 1 (mod s); la raiz candidata es a^alpha
+    let alpha = inv_mod(r, s)?;
+
+    // Raiz r-esima primitiva de la unidad, para los logaritmos discretos
+    let zeta = mod_pow(rho, (p - 1) / r, p);
+
+    // b = a^(r*alpha - 1) vive en el r-subgrupo de Sylow y mide el error
+    let mut b = mod_pow(a, mod_sub(mod_mul(r, alpha, p - 1), 1, p - 1), p);
+    let mut c = mod_pow(rho, s, p);
+    let mut h = 1u64;
+
+    for i in 1..t {
+        let d = mod_pow(b, r.pow(t - 1 - i), p);
+        // j con d * zeta^j = 1 (logaritmo discreto en un grupo de orden r)
+        let mut j = 0u64;
+        let mut acc = d;
+        while acc != 1 {
+            acc = mod_mul(acc, zeta, p);
+            j += 1;
+            if j >= r {
+                return None;
+            }
+        }
+        let cr = mod_pow(c, r, p);
+        b = mod_mul(b, mod_pow(cr, j, p), p);
+        h = mod_mul(h, mod_pow(c, j, p), p);
+        c = cr;
+    }
+
+    let x = mod_mul(mod_pow(a, alpha, p), h, p);
+    if mod_pow(x, r, p) == a {
+        Some(x)
+    } else {
+        None
+    }
+}
+
+/// Raiz cubica de `a` en F_p. None si `a` no es cubo.
+pub fn mod_cbrt(a: u64, p: u64) -> Option<u64> {
+    mod_rth_root(a, 3, p)
+}
+
+/// Todas las raices cubicas de `a` en F_p (0, 1 o 3 valores, ordenados).
+///
+/// Si p ≡ 1 (mod 3) y hay una raiz x, las otras son beta*x y beta^2*x.
+pub fn mod_cbrt_all(a: u64, p: u64) -> Vec<u64> {
+    let x = match mod_cbrt(a, p) {
+        Some(x) => x,
+        None => return Vec::new(),
+    };
+    let mut roots = vec![x];
+    if x != 0 {
+        if let Some(beta) = cube_root_of_unity(p) {
+            let bx = mod_mul(beta, x, p);
+            roots.push(bx);
+            roots.push(mod_mul(beta, bx, p));
+        }
+    }
+    roots.sort_unstable();
+    roots
+}
+
+/// Caracter cubico: a^((p-1)/3) para p ≡ 1 (mod 3).
+///
+/// Retorna 1 si `a` es cubo, una raiz cubica primitiva de 1 si no, y 0 si
+/// a == 0. Para p ≢ 1 (mod 3) todo elemento es cubo y se retorna 1 (o 0).
+pub fn cubic_character(a: u64, p: u64) -> u64 {
+    let a = a % p;
+    if a == 0 {
+        return 0;
+    }
+    if p % 3 != 1 {
+        return 1;
+    }
+    mod_pow(a, (p - 1) / 3, p)
+}
+
+/// Raiz cubica primitiva de 1 obtenida directamente como (-1 + sqrt(-3)) / 2.
+/// None si p ≢ 1 (mod 3), donde no existe.
+pub fn cube_root_of_unity(p: u64) -> Option<u64> {
+    if p % 3 != 1 {
+        return None;
+    }
+    let s = mod_sqrt(p - 3, p)?;
+    Some(mod_mul(mod_sub(s, 1, p), mod_inv(2, p), p))
+}
+
+/// Menor no-residuo cuadratico de F_p (p primo impar).
+pub fn quadratic_non_residue(p: u64) -> Option<u64> {
+    (2..p).find(|&z| legendre(z, p) == p - 1)
+}
@@ -0,0 +1,204 @@
+/// Algoritmo de Schoof: #E(F_p) = p + 1 - t en tiempo polinomico en log p.
+///
+/// Para cada primo pequeno l se calcula t mod l con la ecuacion
+/// caracteristica de Frobenius sobre la l-torsion,
+///
+///   pi^2(P) + [p mod l] P = [t mod l] pi(P),     P in E[l],
+///
+/// trabajando con el punto generico P = (x, y) en F_p[x, y]/(g_l(x), y^2 - f(x)).
+/// Como psi_l no tiene por que ser irreducible, un denominador puede no ser
+/// invertible sin ser nulo: su mcd con el modulo es entonces un factor
+/// propio, y basta con repetir el calculo modulo ese factor (la relacion vale
+/// para cualquier punto de orden l, y [tau] pi(P) determina tau mod l).
+/// t se reconstruye por CRT con primos hasta que su producto supera 4 sqrt(p)
+/// (cota de Hasse); con p de 64 bits basta con l <= 31.
+///
+/// l = 2 se resuelve aparte: t es par sii E tiene un punto de orden 2, es
+/// decir, sii mcd(x^p - x, x^3 + ax + b) != 1.
+
+use super::division::division_polynomial;
+use super::field::*;
+use super::poly::Poly;
+use super::primes::is_prime;
+use super::weierstrass::Curve;
+
+/// Error interno: el denominador comparte un factor propio con el modulo.
+type Split<T> = std::result::Result<T, Poly>;
+
+/// Punto (X(x), Y(x) * y) con coordenadas en F_p[x]/(h); None es O.
+type FormalPoint = Option<(Poly, Poly)>;
+
+/// Aritmetica de curva sobre F_p[x]/(h) con y^2 = f(x).
+struct TorsionRing {
+    h: Poly,
+    f: Poly,
+    a: u64,
+}
+
+impl TorsionRing {
+    fn new(ec: &Curve, h: Poly) -> Self {
+        let f = Poly::new(vec![ec.b, ec.a, 0, 1], ec.p).rem(&h);
+        TorsionRing { h, f, a: ec.a }
+    }
+
+    fn mul(&self, u: &Poly, v: &Poly) -> Poly {
+        u.mul_mod(v, &self.h)
+    }
+
+    /// Inverso de v mod h; None si v ≡ 0 y Err con el factor si el mcd es propio.
+    fn inv(&self, v: &Poly) -> Split<Option<Poly>> {
+        if v.is_zero() {
+            return Ok(None);
+        }
+        let (g, s) = v.gcd_ext(&self.h);
+        if g.degree() == Some(0) {
+            Ok(Some(s))
+        } else {
+            Err(g)
+        }
+    }
+
+    /// v ≡ 0 en todos los puntos, en ninguno, o Err si solo en parte.
+    fn is_zero(&self, v: &Poly) -> Split<bool> {
+        Ok(self.inv(v)?.is_none())
+    }
+
+    fn eq(&self, p1: &FormalPoint, p2: &FormalPoint) -> Split<bool> {
+        match (p1, p2) {
+            (None, None) => Ok(true),
+            (None, _) | (_, None) => Ok(false),
+            (Some((x1, y1)), Some((x2, y2))) => Ok(self.is_zero(&(x1 - x2))? && self.is_zero(&(y1 - y2))?),
+        }
+    }
+
+    /// X3 = f * lambda^2 - X1 - X2, Y3 = lambda (X1 - X3) - Y1 (lambda sin el factor y).
+    fn chord(&self, lambda: &Poly, x1: &Poly, y1: &Poly, x2: &Poly) -> FormalPoint {
+        let x3 = &(&self.mul(&self.f, &self.mul(lambda, lambda)) - x1) - x2;
+        let y3 = &self.mul(lambda, &(x1 - &x3)) - y1;
+        Some((x3, y3))
+    }
+
+    fn double(&self, pt: &FormalPoint) -> Split<FormalPoint> {
+        let Some((x, y)) = pt else { return Ok(None) };
+        // lambda = (3X^2 + a) / (2 y Y) = y (3X^2 + a) / (2 f Y)
+        let Some(inv) = self.inv(&self.mul(&self.f, y).scale(2))? else { return Ok(None) };
+        let num = &self.mul(x, x).scale(3) + &Poly::constant(self.a, self.h.modulus());
+        Ok(self.chord(&self.mul(&num, &inv), x, y, x))
+    }
+
+    fn add(&self, p1: &FormalPoint, p2: &FormalPoint) -> Split<FormalPoint> {
+        let (Some((x1, y1)), Some((x2, y2))) = (p1, p2) else {
+            return Ok(if p1.is_none() { p2.clone() } else { p1.clone() });
+        };
+        match self.inv(&(x1 - x2))? {
+            Some(inv) => Ok(self.chord(&self.mul(&(y1 - y2), &inv), x1, y1, x2)),
+            // X1 = X2 en todos los puntos: P2 = -P1 o P2 = P1
+            None if self.is_zero(&(y1 + y2))? => Ok(None),
+            None if self.is_zero(&(y1 - y2))? => self.double(p1),
+            None => unreachable!("X1 = X2 implica Y1 = ±Y2"),
+        }
+    }
+
+    fn scalar_mul(&self, k: u64, pt: &FormalPoint) -> Split<FormalPoint> {
+        let mut result = None;
+        for i in (0..64 - k.leading_zeros()).rev() {
+            result = self.double(&result)?;
+            if (k >> i) & 1 == 1 {
+                result = self.add(&result, pt)?;
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// t mod l buscando tau con pi^2(P) + [p mod l] P = [tau] pi(P).
+fn trace_mod_l_in(ring: &TorsionRing, l: u64, frob: &[Poly; 4]) -> Split<u64> {
+    let p = ring.h.modulus();
+    let [xp, yp, xp2, yp2] = frob;
+    let generic = Some((Poly::x(p).rem(&ring.h), Poly::one(p)));
+    let pi = Some((xp.clone(), yp.clone()));
+    let pi2 = Some((xp2.clone(), yp2.clone()));
+    let target = ring.add(&pi2, &ring.scalar_mul(p % l, &generic)?)?;
+
+    let mut acc = None;
+    for tau in 0..l {
+        if ring.eq(&acc, &target)? {
+            return Ok(tau);
+        }
+        acc = ring.add(&acc, &pi)?;
+    }
+    unreachable!("la ecuacion de Frobenius siempre tiene solucion mod l")
+}
+
+/// t mod l para un primo l != p.
+pub fn trace_mod_l(ec: &Curve, l: u64) -> u64 {
+    let p = ec.p;
+    assert!(l != p, "Schoof requiere l != p");
+    let f = Poly::new(vec![ec.b, ec.a, 0, 1], p);
+    if l == 2 {
+        let xp = Poly::x(p).pow_mod(p, &f);
+        let g = (&xp - &Poly::x(p)).gcd(&f);
+        return if g.degree() == Some(0) { 1 } else { 0 };
+    }
+
+    let mut h = division_polynomial(ec, l as usize).monic();
+    // (x^p, f^((p-1)/2)) y (x^(p^2), f^((p^2-1)/2)): y^(p^2) = y * yp * yp^p
+    let xp = Poly::x(p).pow_mod(p, &h);
+    let yp = f.pow_mod((p - 1) / 2, &h);
+    let xp2 = xp.pow_mod(p, &h);
+    let yp2 = yp.mul_mod(&yp.pow_mod(p, &h), &h);
+    let mut frob = [xp, yp, xp2, yp2];
+    loop {
+        let ring = TorsionRing::new(ec, h.clone());
+        match trace_mod_l_in(&ring, l, &frob) {
+            Ok(tau) => return tau,
+            Err(g) => {
+                // quedarse con el factor de menor grado; la relacion vale en ambos
+                let cofactor = h.div_rem(&g).0.monic();
+                h = if g.degree() <= cofactor.degree() { g } else { cofactor };
+                frob = frob.map(|v| v.rem(&h));
+            }
+        }
+    }
+}
+
+/// Primos l != p cuyo producto supera 4 sqrt(p).
+pub fn schoof_primes(p: u64) -> Vec<u64> {
+    let mut primes = Vec::new();
+    let mut product: u128 = 1;
+    let mut l = 2u64;
+    while product * product <= 16 * p as u128 {
+        if is_prime(l) && l != p {
+            primes.push(l);
+            product *= l as u128;
+        }
+        l += 1;
+    }
+    primes
+}
+
+/// Traza de Frobenius t = p + 1 - #E(F_p), con |t| <= 2 sqrt(p).
+pub fn schoof_trace(ec: &Curve) -> i64 {
+    assert!(ec.p > 3 && !ec.is_singular(), "Schoof requiere p > 3 y una curva no singular");
+    let (mut t, mut modulus): (u128, u128) = (0, 1);
+    for l in schoof_primes(ec.p) {
+        let tau = trace_mod_l(ec, l) as u128;
+        // CRT: t' = t + modulus * ((tau - t) / modulus mod l)
+        let l128 = l as u128;
+        let diff = (tau + l128 - t % l128) % l128;
+        let inv = mod_inv((modulus % l128) as u64, l) as u128;
+        t += modulus * (diff * inv % l128);
+        modulus *= l128;
+    }
+    if t > modulus / 2 {
+        -((modulus - t) as i64)
+    } else {
+        t as i64
+    }
+}
+
+/// #E(F_p) por Schoof, en u128: con p a menos de 2^33 de 2^64,
+/// p + 1 - t puede pasar de u64.
+pub fn schoof_count(ec: &Curve) -> u128 {
+    (ec.p as i128 + 1 - schoof_trace(ec) as i128) as u128
+}
@@ -0,0 +1,98 @@
+/// Codificacion SEC1 de puntos (SEC 1 v2, seccion 2.3.3-2.3.4).
+///
+/// - 0x00: punto en el infinito (un solo byte).
+/// - 0x02 / 0x03 || X: comprimido; el prefijo da la paridad de y.
+/// - 0x04 || X || Y: sin comprimir.
+///
+/// Las coordenadas van en big-endian con la longitud fija de p en bytes.
+/// Al decodificar se rechazan coordenadas >= p y puntos fuera de la curva;
+/// la forma comprimida recupera y con `mod_sqrt`.
+
+use crate::error::{Error, Result};
+use super::curve::Point;
+use super::field::*;
+use super::weierstrass::Curve;
+
+pub const SEC1_INFINITY: u8 = 0x00;
+pub const SEC1_EVEN: u8 = 0x02;
+pub const SEC1_ODD: u8 = 0x03;
+pub const SEC1_UNCOMPRESSED: u8 = 0x04;
+
+/// Bytes de una coordenada de F_p: ceil(log2(p) / 8).
+pub fn field_len(p: u64) -> usize {
+    (64 - p.leading_zeros() as usize).div_ceil(8)
+}
+
+fn write_coord(out: &mut Vec<u8>, v: u64, len: usize) {
+    out.extend_from_slice(&v.to_be_bytes()[8 - len..]);
+}
+
+fn read_coord(bytes: &[u8], p: u64) -> Result<u64> {
+    let v = bytes.iter().fold(0u64, |acc, &b| (acc << 8) | b as u64);
+    if v >= p {
+        return Err(Error::InvalidEncoding { reason: "coordenada >= p" });
+    }
+    Ok(v)
+}
+
+/// Codifica P en SEC1, comprimido (33 bytes en secp256k1) o no.
+pub fn encode_point(pt: &Point, p: u64, compressed: bool) -> Vec<u8> {
+    if pt.infinity {
+        return vec![SEC1_INFINITY];
+    }
+    let len = field_len(p);
+    let mut out = Vec::with_capacity(1 + 2 * len);
+    if compressed {
+        out.push(if pt.y & 1 == 0 { SEC1_EVEN } else { SEC1_ODD });
+        write_coord(&mut out, pt.x, len);
+    } else {
+        out.push(SEC1_UNCOMPRESSED);
+        write_coord(&mut out, pt.x, len);
+        write_coord(&mut out, pt.y, len);
+    }
+    out
+}
+
+/// Decodifica un punto SEC1 de y^2 = x^3 + b.
+pub fn decode_point(bytes: &[u8], b: u64, p: u64) -> Result<Point> {
+    decode_point_on(&Curve::new(0, b, p), bytes)
+}
+
+/// Decodifica un punto SEC1 de y^2 = x^3 + ax + b, comprobando que este en
+/// la curva. Errores: `InvalidEncoding` por prefijo, longitud o rango, y
+/// `PointNotOnCurve` si (x, y) no cumple la ecuacion o x^3 + ax + b no es
+/// cuadrado (forma comprimida).
+pub fn decode_point_on(ec: &Curve, bytes: &[u8]) -> Result<Point> {
+    let p = ec.p;
+    let len = field_len(p);
+    let (&prefix, body) = bytes
+        .split_first()
+        .ok_or(Error::InvalidEncoding { reason: "entrada vacia" })?;
+
+    match prefix {
+        SEC1_INFINITY if body.is_empty() => Ok(Point::infinity()),
+        SEC1_EVEN | SEC1_ODD if body.len() == len => {
+            let x = read_coord(body, p)?;
+            let rhs = mod_add(mod_add(mod_pow(x, 3, p), mod_mul(ec.a, x, p), p), ec.b, p);
+            let y = mod_sqrt(rhs, p).ok_or(Error::PointNotOnCurve)?;
+            let odd = prefix == SEC1_ODD;
+            if y == 0 && odd {
+                // y = 0 solo admite el prefijo par
+                return Err(Error::PointNotOnCurve);
+            }
+            let y = if (y & 1 == 1) == odd { y } else { p - y };
+            Ok(Point::new(x, y))
+        }
+        SEC1_UNCOMPRESSED if body.len() == 2 * len => {
+            let pt = Point::new(read_coord(&body[..len], p)?, read_coord(&body[len..], p)?);
+            if !ec.is_on_curve(&pt) {
+                return Err(Error::PointNotOnCurve);
+            }
+            Ok(pt)
+        }
+        SEC1_INFINITY | SEC1_EVEN | SEC1_ODD | SEC1_UNCOMPRESSED => {
+            Err(Error::InvalidEncoding { reason: "longitud incorrecta" })
+        }
+        _ => Err(Error::InvalidEncoding { reason: "prefijo desconocido" }),
+    }
+}
@@ -0,0 +1,144 @@
+/// Modo de tiempo constante para secp256k1.
+///
+/// La ley de grupo afin de `math::curve` ramifica segun P == Q, P == -Q o
+/// P == O y llama a `inv` en cada suma: el patron de ejecucion depende de
+/// los bits secretos del escalar. Aqui los puntos van en coordenadas
+/// proyectivas homogeneas (X:Y:Z) con las formulas completas de
+/// Renes-Costello-Batina (2016, algoritmos 7 y 9 para a = 0), que son
+/// correctas para cualquier par de entradas, incluido el infinito (0:1:0).
+/// La multiplicacion escalar es una escalera de Montgomery de 256 pasos
+/// fijos con intercambios condicionales por mascara.
+
+use super::*;
+
+/// 3*b = 21, la unica constante de curva que usan las formulas completas.
+const B3: FieldElement = FieldElement::from_raw([21, 0, 0, 0]);
+
+/// Punto en coordenadas proyectivas homogeneas: (X:Y:Z) representa (X/Z, Y/Z).
+#[derive(Clone, Copy, Debug)]
+pub struct ProjectivePoint {
+    pub x: FieldElement,
+    pub y: FieldElement,
+    pub z: FieldElement,
+}
+
+impl ProjectivePoint {
+    /// Punto en el infinito (0:1:0).
+    pub const IDENTITY: Self = ProjectivePoint {
+        x: FieldElement::ZERO,
+        y: FieldElement::ONE,
+        z: FieldElement::ZERO,
+    };
+
+    pub fn from_affine(pt: &Affine) -> Self {
+        match pt {
+            None => Self::IDENTITY,
+            Some((x, y)) => ProjectivePoint { x: *x, y: *y, z: FieldElement::ONE },
+        }
+    }
+
+    /// Vuelve a afin con una unica inversion de Fermat (sin ramas sobre Z).
+    pub fn to_affine(&self) -> Affine {
+        let z_inv = self.z.invert_ct();
+        let pt = (self.x * z_inv, self.y * z_inv);
+        // El resultado de la multiplicacion es publico: ramificar aqui no filtra k
+        if self.z.is_zero() {
+            None
+        } else {
+            Some(pt)
+        }
+    }
+
+    /// Devuelve `a` si choice == 0 y `b` si choice == 1, sin ramas.
+    pub fn conditional_select(a: &Self, b: &Self, choice: u64) -> Self {
+        ProjectivePoint {
+            x: FieldElement::conditional_select(&a.x, &b.x, choice),
+            y: FieldElement::conditional_select(&a.y, &b.y, choice),
+            z: FieldElement::conditional_select(&a.z, &b.z, choice),
+        }
+    }
+
+    /// Intercambia `a` y `b` si choice == 1, sin ramas.
+    pub fn conditional_swap(a: &mut Self, b: &mut Self, choice: u64) {
+        let (na, nb) = (
+            Self::conditional_select(a, b, choice),
+            Self::conditional_select(b, a, choice),
+        );
+        *a = na;
+        *b = nb;
+    }
+
+    /// Suma completa (RCB16, algoritmo 7): 12M + 2 mult. por 3b.
+    pub fn add(&self, other: &Self) -> Self {
+        let (x1, y1, z1) = (self.x, self.y, self.z);
+        let (x2, y2, z2) = (other.x, other.y, other.z);
+
+        let t0 = x1 * x2;
+        let t1 = y1 * y2;
+        let t2 = z1 * z2;
+        let t3 = (x1 + y1) * (x2 + y2) - (t0 + t1);
+        let t4 = (y1 + z1) * (y2 + z2) - (t1 + t2);
+        let y3 = (x1 + z1) * (x2 + z2) - (t0 + t2);
+        let t0 = t0 + t0 + t0;
+        let t2 = B3 * t2;
+        let z3 = t1 + t2;
+        let t1 = t1 - t2;
+        let y3 = B3 * y3;
+
+        ProjectivePoint {
+            x: t3 * t1 - t4 * y3,
+            y: t1 * z3 + y3 * t0,
+            z: z3 * t4 + t0 * t3,
+        }
+    }
+
+    /// Doblado completo (RCB16, algoritmo 9): 6M + 2S + 1 mult. por 3b.
+    pub fn double(&self) -> Self {
+        let (x, y, z) = (self.x, self.y, self.z);
+
+        let t0 = y.square();
+        let z3 = t0.double().double().double();
+        let t1 = y * z;
+        let t2 = B3 * z.square();
+        let x3 = t2 * z3;
+        let y3 = t0 + t2;
+        let z3 = t1 * z3;
+        let t2 = t2 + t2 + t2;
+        let t0 = t0 - t2;
+        let y3 = x3 + t0 * y3;
+        let x3 = (t0 * (x * y)).double();
+
+        ProjectivePoint { x: x3, y: y3, z: z3 }
+    }
+
+    pub fn neg(&self) -> Self {
+        ProjectivePoint { x: self.x, y: -self.y, z: self.z }
+    }
+}
+
+/// Escalera de Montgomery sobre los 256 bits de k, en tiempo constante.
+///
+/// Invariante: R1 - R0 = P. Cada paso hace exactamente una suma y un
+/// doblado completos; el bit solo decide un intercambio por mascara.
+pub fn ladder_mul(k: &Scalar, pt: &ProjectivePoint) -> ProjectivePoint {
+    let mut r0 = ProjectivePoint::IDENTITY;
+    let mut r1 = *pt;
+    for i in (0..256).rev() {
+        let b = k.bit(i);
+        ProjectivePoint::conditional_swap(&mut r0, &mut r1, b);
+        r1 = r0.add(&r1);
+        r0 = r0.double();
+        ProjectivePoint::conditional_swap(&mut r0, &mut r1, b);
+    }
+    r0
+}
+
+/// k*P en tiempo constante respecto de k, con entrada y salida afines.
+pub fn scalar_mul_ct(k: &Scalar, pt: &Affine) -> Affine {
+    ladder_mul(k, &ProjectivePoint::from_affine(pt)).to_affine()
+}
+
+/// k*G en tiempo constante: derivacion de claves publicas y nonces.
+pub fn mul_generator_ct(k: &Scalar) -> Affine {
+    scalar_mul_ct(k, &GENERATOR)
+}
@@ -0,0 +1,214 @@
+/// Cuerpo base de secp256k1: F_p con p = 2^256 - 2^32 - 977.
+///
+/// Como 2^256 ≡ 2^32 + 977 (mod p), un producto de 512 bits se reduce
+/// plegando la mitad alta multiplicada por esa constante de 33 bits, sin
+/// ninguna division. Los elementos se guardan siempre reducidos en [0, p).
+
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use num_bigint::BigUint;
+
+use super::limbs::*;
+use crate::math::fp::Field;
+
+/// p = 2^256 - 2^32 - 977 en limbs little-endian.
+pub const P: [u64; 4] = [
+    0xFFFFFFFEFFFFFC2F, 0xFFFFFFFFFFFFFFFF, 0xFFFFFFFFFFFFFFFF, 0xFFFFFFFFFFFFFFFF,
+];
+
+/// 2^256 - p = 2^32 + 977.
+const C: u64 = 0x1_0000_03D1;
+
+const P_MINUS_2: [u64; 4] = [
+    0xFFFFFFFEFFFFFC2D, 0xFFFFFFFFFFFFFFFF, 0xFFFFFFFFFFFFFFFF, 0xFFFFFFFFFFFFFFFF,
+];
+
+/// (p + 1) / 4: exponente de la raiz cuadrada, ya que p ≡ 3 (mod 4).
+const P_PLUS_1_DIV_4: [u64; 4] = [
+    0xFFFFFFFFBFFFFF0C, 0xFFFFFFFFFFFFFFFF, 0xFFFFFFFFFFFFFFFF, 0x3FFFFFFFFFFFFFFF,
+];
+
+/// Elemento de F_p para secp256k1 (4 limbs de 64 bits).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FieldElement([u64; 4]);
+
+impl FieldElement {
+    pub const ZERO: Self = FieldElement([0, 0, 0, 0]);
+    pub const ONE: Self = FieldElement([1, 0, 0, 0]);
+
+    /// Construye desde limbs little-endian ya reducidos (< p). Para constantes.
+    pub const fn from_raw(limbs: [u64; 4]) -> Self {
+        FieldElement(limbs)
+    }
+
+    pub fn from_u64(v: u64) -> Self {
+        FieldElement([v, 0, 0, 0])
+    }
+
+    /// Decodifica 32 bytes big-endian. None si el valor no es < p.
+    pub fn from_be_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        let limbs = from_be_bytes(bytes);
+        let (_, borrow) = sub4(&limbs, &P);
+        if borrow == 1 {
+            Some(FieldElement(limbs))
+        } else {
+            None
+        }
+    }
+
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        to_be_bytes(&self.0)
+    }
+
+    /// Conversion desde el backend BigUint (reduce modulo p).
+    pub fn from_biguint(v: &BigUint) -> Self {
+        FieldElement(reduce_biguint(v, &P))
+    }
+
+    pub fn to_biguint(&self) -> BigUint {
+        BigUint::from_bytes_be(&self.to_be_bytes())
+    }
+
+    pub fn limbs(&self) -> [u64; 4] {
+        self.0
+    }
+
+    pub fn is_odd(&self) -> bool {
+        self.0[0] & 1 == 1
+    }
+
+    /// Devuelve `a` si choice == 0 y `b` si choice == 1, sin ramas.
+    pub fn conditional_select(a: &Self, b: &Self, choice: u64) -> Self {
+        FieldElement(select4(mask(choice), &a.0, &b.0))
+    }
+
+    /// Igualdad sin cortocircuito; 1 si son iguales, 0 si no.
+    pub fn ct_eq(&self, other: &Self) -> u64 {
+        eq4(&self.0, &other.0) as u64
+    }
+
+    /// Inverso a^(p-2) sin ramas sobre el valor; devuelve 0 para 0.
+    pub fn invert_ct(&self) -> Self {
+        self.pow_limbs(&P_MINUS_2)
+    }
+
+    /// self^e con exponente de 256 bits. Tiempo variable en `e` (publico).
+    pub fn pow_limbs(&self, e: &[u64; 4]) -> Self {
+        let mut result = Self::ONE;
+        for i in (0..256).rev() {
+            result = result.square();
+            if bit4(e, i) == 1 {
+                result = result * *self;
+            }
+        }
+        result
+    }
+}
+
+/// Reduce t + carry*2^256 a [0, p), suponiendo que el valor es < 2p.
+#[inline(always)]
+fn normalize(t: [u64; 4], carry: u64) -> [u64; 4] {
+    // t >= p  <=>  t + C desborda 2^256
+    let (u, c2) = add4(&t, &[C, 0, 0, 0]);
+    select4(mask(carry | c2), &t, &u)
+}
+
+/// Reduccion de un producto de 512 bits usando 2^256 ≡ C (mod p).
+#[inline(always)]
+fn reduce_wide(w: [u64; 8]) -> [u64; 4] {
+    // Primer plegado: lo + hi*C  (< 2^290)
+    let mut r = [0u64; 4];
+    let mut carry = 0u128;
+    for i in 0..4 {
+        let t = w[i] as u128 + (w[i + 4] as u128) * (C as u128) + carry;
+        r[i] = t as u64;
+        carry = t >> 64;
+    }
+    // Segundo plegado: el limb superior (< 2^34) vuelve a multiplicarse por C
+    let mut c = carry * (C as u128);
+    for limb in r.iter_mut() {
+        let t = *limb as u128 + c;
+        *limb = t as u64;
+        c = t >> 64;
+    }
+    normalize(r, c as u64)
+}
+
+impl Add for FieldElement {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        let (t, carry) = add4(&self.0, &rhs.0);
+        FieldElement(normalize(t, carry))
+    }
+}
+
+impl Sub for FieldElement {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        let (t, borrow) = sub4(&self.0, &rhs.0);
+        // Si hubo prestamo, sumar p equivale a restar C modulo 2^256
+        let (u, _) = sub4(&t, &[C, 0, 0, 0]);
+        FieldElement(select4(mask(borrow), &t, &u))
+    }
+}
+
+impl Mul for FieldElement {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        FieldElement(reduce_wide(mul_wide(&self.0, &rhs.0)))
+    }
+}
+
+impl Neg for FieldElement {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::ZERO - self
+    }
+}
+
+impl Field for FieldElement {
+    fn zero(&self) -> Self {
+        Self::ZERO
+    }
+
+    fn one(&self) -> Self {
+        Self::ONE
+    }
+
+    fn lift(&self, v: u64) -> Self {
+        Self::from_u64(v)
+    }
+
+    fn is_zero(&self) -> bool {
+        eq4(&self.0, &Self::ZERO.0)
+    }
+
+    /// Inverso por el pequeno teorema de Fermat: a^(p-2).
+    fn inv(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        Some(self.pow_limbs(&P_MINUS_2))
+    }
+
+    /// Raiz cuadrada a^((p+1)/4), valida porque p ≡ 3 (mod 4).
+    fn sqrt(&self) -> Option<Self> {
+        let r = self.pow_limbs(&P_PLUS_1_DIV_4);
+        if r.square() == *self {
+            Some(r)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for FieldElement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "0x")?;
+        for limb in self.0.iter().rev() {
+            write!(f, "{:016x}", limb)?;
+        }
+        Ok(())
+    }
+}
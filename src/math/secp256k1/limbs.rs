@@ -0,0 +1,124 @@
+/// Primitivas sobre enteros de 256 bits en 4 limbs u64 (little-endian).
+///
+/// Sin ramas dependientes de los datos: los acarreos se propagan con u128
+/// y las selecciones se hacen con mascaras.
+
+use num_bigint::BigUint;
+
+/// Suma con acarreo: (a + b + carry) -> (resultado, acarreo).
+#[inline(always)]
+pub(crate) fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + b as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+/// Resta con prestamo: (a - b - borrow) -> (resultado, prestamo en {0, 1}).
+#[inline(always)]
+pub(crate) fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let t = (a as u128).wrapping_sub(b as u128 + borrow as u128);
+    (t as u64, ((t >> 64) as u64) & 1)
+}
+
+/// a + b sobre 256 bits; devuelve el acarreo de salida.
+#[inline(always)]
+pub(crate) fn add4(a: &[u64; 4], b: &[u64; 4]) -> ([u64; 4], u64) {
+    let mut r = [0u64; 4];
+    let mut c = 0;
+    for i in 0..4 {
+        (r[i], c) = adc(a[i], b[i], c);
+    }
+    (r, c)
+}
+
+/// a - b sobre 256 bits; devuelve el prestamo de salida.
+#[inline(always)]
+pub(crate) fn sub4(a: &[u64; 4], b: &[u64; 4]) -> ([u64; 4], u64) {
+    let mut r = [0u64; 4];
+    let mut borrow = 0;
+    for i in 0..4 {
+        (r[i], borrow) = sbb(a[i], b[i], borrow);
+    }
+    (r, borrow)
+}
+
+/// Producto completo 256x256 -> 512 bits (schoolbook).
+#[inline(always)]
+pub(crate) fn mul_wide(a: &[u64; 4], b: &[u64; 4]) -> [u64; 8] {
+    let mut w = [0u64; 8];
+    for i in 0..4 {
+        let mut carry = 0u128;
+        for j in 0..4 {
+            let t = w[i + j] as u128 + (a[i] as u128) * (b[j] as u128) + carry;
+            w[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        w[i + 4] = carry as u64;
+    }
+    w
+}
+
+/// Mascara de todo unos si `bit == 1`, de ceros si `bit == 0`.
+#[inline(always)]
+pub(crate) fn mask(bit: u64) -> u64 {
+    0u64.wrapping_sub(bit)
+}
+
+/// Devuelve `a` si la mascara es 0, `b` si es todo unos.
+#[inline(always)]
+pub(crate) fn select4(m: u64, a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+    let mut r = [0u64; 4];
+    for i in 0..4 {
+        r[i] = (a[i] & !m) | (b[i] & m);
+    }
+    r
+}
+
+/// Comparacion a == b sin cortocircuito.
+#[inline(always)]
+pub(crate) fn eq4(a: &[u64; 4], b: &[u64; 4]) -> bool {
+    let mut diff = 0u64;
+    for i in 0..4 {
+        diff |= a[i] ^ b[i];
+    }
+    // 1 sii diff == 0, sin comparar contra cero
+    ((diff | diff.wrapping_neg()) >> 63) ^ 1 == 1
+}
+
+/// Bit i (0 = menos significativo) de un entero de 4 limbs.
+#[inline(always)]
+pub(crate) fn bit4(a: &[u64; 4], i: usize) -> u64 {
+    (a[i / 64] >> (i % 64)) & 1
+}
+
+pub(crate) fn from_be_bytes(bytes: &[u8; 32]) -> [u64; 4] {
+    let mut r = [0u64; 4];
+    for (i, limb) in r.iter_mut().enumerate() {
+        let start = 32 - 8 * (i + 1);
+        *limb = u64::from_be_bytes(bytes[start..start + 8].try_into().expect("8 bytes"));
+    }
+    r
+}
+
+pub(crate) fn to_be_bytes(a: &[u64; 4]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    for (i, limb) in a.iter().enumerate() {
+        let start = 32 - 8 * (i + 1);
+        out[start..start + 8].copy_from_slice(&limb.to_be_bytes());
+    }
+    out
+}
+
+/// Limbs u64 little-endian a BigUint (via digitos u32 para `BigUint::from_slice`).
+pub fn limbs_to_biguint(limbs: &[u64; 4]) -> BigUint {
+    let digits: Vec<u32> = limbs.iter().flat_map(|&l| [l as u32, (l >> 32) as u32]).collect();
+    BigUint::from_slice(&digits)
+}
+
+/// Reduce un BigUint modulo `m` y lo devuelve en limbs.
+pub(crate) fn reduce_biguint(v: &BigUint, m: &[u64; 4]) -> [u64; 4] {
+    let reduced = v % BigUint::from_bytes_be(&to_be_bytes(m));
+    let be = reduced.to_bytes_be();
+    let mut bytes = [0u8; 32];
+    bytes[32 - be.len()..].copy_from_slice(&be);
+    from_be_bytes(&bytes)
+}
@@ -0,0 +1,78 @@
+/// Aritmetica dedicada de ancho fijo para secp256k1 (4 limbs de 64 bits).
+///
+/// `FieldElement` (mod p) y `Scalar` (mod n) implementan `Field`, de modo
+/// que la ley de grupo generica de `math::curve` funciona sobre la curva real
+/// sin pasar por BigUint. Los puntos son `Affine` (None = infinito).
+/// `ct` ofrece la multiplicacion escalar en tiempo constante para claves secretas.
+
+mod limbs;
+pub mod field;
+pub mod scalar;
+pub mod ct;
+
+pub use field::FieldElement;
+pub use scalar::Scalar;
+pub use limbs::limbs_to_biguint;
+
+use std::sync::OnceLock;
+
+use crate::math::curve::*;
+use crate::math::fp::Field;
+use crate::math::wnaf::*;
+
+/// Punto afin de secp256k1; None representa el punto en el infinito.
+pub type Affine = Option<(FieldElement, FieldElement)>;
+
+/// Coeficiente b de y^2 = x^3 + 7.
+pub const B: FieldElement = FieldElement::from_raw([7, 0, 0, 0]);
+
+/// Generador G (SEC 2, seccion 2.4.1).
+pub const GENERATOR: Affine = Some((
+    FieldElement::from_raw([
+        0x59F2815B16F81798, 0x029BFCDB2DCE28D9, 0x55A06295CE870B07, 0x79BE667EF9DCBBAC,
+    ]),
+    FieldElement::from_raw([
+        0x9C47D08FFB10D4B8, 0xFD17B448A6855419, 0x5DA4FBFC0E1108A8, 0x483ADA7726A3C465,
+    ]),
+));
+
+/// Verifica si P esta en la curva y^2 = x^3 + 7.
+pub fn is_on_curve(pt: &Affine) -> bool {
+    match pt {
+        None => true,
+        Some((x, y)) => y.square() == x.square() * *x + B,
+    }
+}
+
+pub fn point_add(p1: &Affine, p2: &Affine) -> Affine {
+    affine_add(*p1, *p2)
+}
+
+pub fn point_double(pt: &Affine) -> Affine {
+    affine_double(*pt)
+}
+
+pub fn point_neg(pt: &Affine) -> Affine {
+    pt.map(|(x, y)| (x, -y))
+}
+
+/// Ventana wNAF para escalares de 256 bits.
+const WNAF_WINDOW: u32 = 5;
+
+/// Dientes del comb de G: tabla de 2^8 - 1 puntos y 32 doblados por k*G.
+const COMB_TEETH: usize = 8;
+
+/// Multiplicacion escalar k*P por wNAF (ventana 5) en coordenadas jacobianas.
+/// Tiempo variable: no usar con escalares secretos (ver `ct`).
+pub fn scalar_mul(k: &Scalar, pt: &Affine) -> Affine {
+    wnaf_mul(&k.limbs(), *pt, WNAF_WINDOW)
+}
+
+/// k*G con el comb de base fija; la tabla se construye en el primer uso.
+/// Tiempo variable, como `scalar_mul`.
+pub fn mul_generator(k: &Scalar) -> Affine {
+    static TABLE: OnceLock<CombTable<FieldElement>> = OnceLock::new();
+    TABLE
+        .get_or_init(|| CombTable::new(GENERATOR.expect("G != O"), 256, COMB_TEETH))
+        .mul(&k.limbs())
+}
@@ -0,0 +1,192 @@
+/// Cuerpo de escalares de secp256k1: Z/nZ con n el orden (primo) de G.
+///
+/// n = 2^256 - NC con NC de 129 bits, asi que la reduccion de 512 bits
+/// pliega la mitad alta por NC un numero fijo de veces (sin divisiones).
+
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use num_bigint::BigUint;
+
+use super::limbs::*;
+use crate::math::big;
+use crate::math::fp::Field;
+
+/// n en limbs little-endian.
+pub const N: [u64; 4] = [
+    0xBFD25E8CD0364141, 0xBAAEDCE6AF48A03B, 0xFFFFFFFFFFFFFFFE, 0xFFFFFFFFFFFFFFFF,
+];
+
+/// 2^256 - n.
+const NC: [u64; 4] = [0x402DA1732FC9BEBF, 0x4551231950B75FC4, 0x1, 0x0];
+
+const N_MINUS_2: [u64; 4] = [
+    0xBFD25E8CD036413F, 0xBAAEDCE6AF48A03B, 0xFFFFFFFFFFFFFFFE, 0xFFFFFFFFFFFFFFFF,
+];
+
+/// Escalar modulo n (4 limbs de 64 bits), siempre reducido en [0, n).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Scalar([u64; 4]);
+
+impl Scalar {
+    pub const ZERO: Self = Scalar([0, 0, 0, 0]);
+    pub const ONE: Self = Scalar([1, 0, 0, 0]);
+
+    /// Construye desde limbs little-endian ya reducidos (< n). Para constantes.
+    pub const fn from_raw(limbs: [u64; 4]) -> Self {
+        Scalar(limbs)
+    }
+
+    pub fn from_u64(v: u64) -> Self {
+        Scalar([v, 0, 0, 0])
+    }
+
+    /// Decodifica 32 bytes big-endian reduciendo modulo n.
+    pub fn from_be_bytes_reduced(bytes: &[u8; 32]) -> Self {
+        Scalar(cond_sub_n(from_be_bytes(bytes), 0))
+    }
+
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        to_be_bytes(&self.0)
+    }
+
+    /// Conversion desde el backend BigUint (reduce modulo n).
+    pub fn from_biguint(v: &BigUint) -> Self {
+        Scalar(reduce_biguint(v, &N))
+    }
+
+    pub fn to_biguint(&self) -> BigUint {
+        BigUint::from_bytes_be(&self.to_be_bytes())
+    }
+
+    pub fn limbs(&self) -> [u64; 4] {
+        self.0
+    }
+
+    /// Devuelve `a` si choice == 0 y `b` si choice == 1, sin ramas.
+    pub fn conditional_select(a: &Self, b: &Self, choice: u64) -> Self {
+        Scalar(select4(mask(choice), &a.0, &b.0))
+    }
+
+    /// Bit i del escalar (0 = menos significativo).
+    pub fn bit(&self, i: usize) -> u64 {
+        bit4(&self.0, i)
+    }
+
+    /// Inverso a^(n-2) sin ramas sobre el valor; devuelve 0 para 0.
+    pub fn invert_ct(&self) -> Self {
+        self.pow_limbs(&N_MINUS_2)
+    }
+
+    /// self^e con exponente de 256 bits. Tiempo variable en `e` (publico).
+    pub fn pow_limbs(&self, e: &[u64; 4]) -> Self {
+        let mut result = Self::ONE;
+        for i in (0..256).rev() {
+            result = result.square();
+            if bit4(e, i) == 1 {
+                result = result * *self;
+            }
+        }
+        result
+    }
+}
+
+/// Resta n si t + carry*2^256 >= n (el valor debe ser < 2n).
+#[inline(always)]
+fn cond_sub_n(t: [u64; 4], carry: u64) -> [u64; 4] {
+    let (u, borrow) = sub4(&t, &N);
+    select4(mask(carry | (borrow ^ 1)), &t, &u)
+}
+
+/// Reduccion de 512 bits usando 2^256 ≡ NC (mod n).
+///
+/// Cuatro plegados bastan: la parte alta pasa de 256 a 130, 4 y 1 bit,
+/// y el ultimo deja el valor por debajo de 2^256.
+#[inline(always)]
+fn reduce_wide(w: [u64; 8]) -> [u64; 4] {
+    let mut acc = w;
+    for _ in 0..4 {
+        let hi = [acc[4], acc[5], acc[6], acc[7]];
+        let prod = mul_wide(&hi, &NC);
+        let mut c = 0;
+        for i in 0..8 {
+            let lo = if i < 4 { acc[i] } else { 0 };
+            (acc[i], c) = adc(lo, prod[i], c);
+        }
+    }
+    debug_assert!(acc[4..].iter().all(|&l| l == 0));
+    cond_sub_n([acc[0], acc[1], acc[2], acc[3]], 0)
+}
+
+impl Add for Scalar {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        let (t, carry) = add4(&self.0, &rhs.0);
+        Scalar(cond_sub_n(t, carry))
+    }
+}
+
+impl Sub for Scalar {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        let (t, borrow) = sub4(&self.0, &rhs.0);
+        let (u, _) = add4(&t, &N);
+        Scalar(select4(mask(borrow), &t, &u))
+    }
+}
+
+impl Mul for Scalar {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Scalar(reduce_wide(mul_wide(&self.0, &rhs.0)))
+    }
+}
+
+impl Neg for Scalar {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::ZERO - self
+    }
+}
+
+impl Field for Scalar {
+    fn zero(&self) -> Self {
+        Self::ZERO
+    }
+
+    fn one(&self) -> Self {
+        Self::ONE
+    }
+
+    fn lift(&self, v: u64) -> Self {
+        Self::from_u64(v)
+    }
+
+    fn is_zero(&self) -> bool {
+        eq4(&self.0, &Self::ZERO.0)
+    }
+
+    /// Inverso por el pequeno teorema de Fermat: a^(n-2).
+    fn inv(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        Some(self.pow_limbs(&N_MINUS_2))
+    }
+
+    /// n ≡ 1 (mod 4): se delega en Tonelli-Shanks del backend BigUint.
+    fn sqrt(&self) -> Option<Self> {
+        let n = BigUint::from_bytes_be(&to_be_bytes(&N));
+        big::mod_sqrt(&self.to_biguint(), &n).map(|r| Scalar::from_biguint(&r))
+    }
+}
+
+impl fmt::Display for Scalar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "0x")?;
+        for limb in self.0.iter().rev() {
+            write!(f, "{:016x}", limb)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+/// l-torsion racional E(F_p)[l] a partir de los polinomios de division.
+///
+/// Las abscisas de E[l] \ {O} sobre la clausura algebraica son las raices
+/// de `torsion_polynomial(ec, l)`; las racionales salen de `Poly::roots`,
+/// y cada x con x^3 + ax + b cuadrado da los puntos (x, ±y). Por el
+/// emparejamiento de Weil, E(F_p)[l] es 0, Z/l o Z/l x Z/l, y el caso
+/// Z/l x Z/l exige l | p - 1.
+///
+/// En y^2 = x^3 + b (j = 0), psi_3 = 3x (x^3 + 4b): x = 0 es siempre raiz,
+/// y {O, (0, ±sqrt(b))} = ker(1 - phi) es el subgrupo de orden 3 que
+/// anula 1 - zeta3. Es racional si y solo si b es cuadrado en F_p; las
+/// otras abscisas, x^3 = -4b, completan E[3] cuando ademas p = 1 mod 3 y
+/// -4b es un cubo.
+
+use std::fmt;
+
+use super::curve::Point;
+use super::division::torsion_polynomial;
+use super::field::*;
+use super::primes::*;
+use super::weierstrass::Curve;
+
+/// E(F_p)[l] para l primo: sus puntos finitos y su rango como F_l-espacio.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TorsionSubgroup {
+    pub l: u64,
+    /// Puntos de orden l, ordenados por (x, y); O no se incluye.
+    pub points: Vec<Point>,
+    /// 0, 1 o 2: E(F_p)[l] ≅ (Z/l)^rank.
+    pub rank: u32,
+}
+
+impl TorsionSubgroup {
+    /// |E(F_p)[l]| = l^rank.
+    pub fn order(&self) -> u64 {
+        self.points.len() as u64 + 1
+    }
+
+    /// E[l] completo: todo E[l] es racional.
+    pub fn is_full(&self) -> bool {
+        self.rank == 2
+    }
+}
+
+impl fmt::Display for TorsionSubgroup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.rank {
+            0 => write!(f, "0"),
+            1 => write!(f, "Z/{}", self.l),
+            _ => write!(f, "Z/{} x Z/{}", self.l, self.l),
+        }
+    }
+}
+
+impl Curve {
+    /// E(F_p)[l] para l primo distinto de p, por raices del polinomio de
+    /// division: coste O(l^2) en grado, pensado para l pequeno. Panics si l
+    /// no es primo o l = p.
+    pub fn rational_torsion(&self, l: u64) -> TorsionSubgroup {
+        assert!(is_prime(l), "l = {} no es primo", l);
+        assert!(l != self.p, "E[p] no se obtiene de psi_p en caracteristica p");
+        let p = self.p;
+        let mut points = Vec::new();
+        for x in torsion_polynomial(self, l as usize).roots() {
+            let rhs = mod_add(mod_add(mod_pow(x, 3, p), mod_mul(self.a, x, p), p), self.b, p);
+            if rhs == 0 {
+                points.push(Point::new(x, 0));
+            } else if let Some(y) = mod_sqrt(rhs, p) {
+                let neg = mod_sub(0, y, p);
+                points.push(Point::new(x, y.min(neg)));
+                points.push(Point::new(x, y.max(neg)));
+            }
+        }
+        let order = points.len() as u64 + 1;
+        debug_assert!(order == 1 || order == l || order == l * l, "|E[l]| no es potencia de l");
+        let rank = if order == 1 { 0 } else if order == l { 1 } else { 2 };
+        TorsionSubgroup { l, points, rank }
+    }
+
+    /// E(F_p)[l] para cada primo l <= `bound` con l != p.
+    pub fn torsion_report(&self, bound: u64) -> Vec<TorsionSubgroup> {
+        (2..=bound)
+            .filter(|&l| is_prime(l) && l != self.p)
+            .map(|l| self.rational_torsion(l))
+            .collect()
+    }
+}
+
+/// `torsion_report` para y^2 = x^3 + b sobre F_p.
+pub fn torsion_report(b: u64, p: u64, bound: u64) -> Vec<TorsionSubgroup> {
+    Curve::new(0, b, p).torsion_report(bound)
+}
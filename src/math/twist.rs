@@ -0,0 +1,135 @@
+/// Twists sexticos de y^2 = x^3 + b (j = 0) sobre F_p con p ≡ 1 (mod 3).
+///
+/// y^2 = x^3 + b y y^2 = x^3 + b' son isomorfas sobre F_p sii b'/b es una
+/// potencia sexta, asi que los twists son las clases de F_p* / (F_p*)^6, un
+/// grupo ciclico de orden 6. Con g ni cuadrado ni cubo, b_i = b g^i
+/// (i = 0..5) recorre las seis clases; b_3 es el twist cuadratico y b_2, b_4
+/// los cubicos.
+///
+/// El isomorfismo E_b -> E_b' es (x, y) -> (delta^2 x, delta^3 y) con
+/// delta^6 = b'/b, definido sobre F_p(delta): grado 1, 2, 3 o 6 segun el
+/// orden de la clase de b'/b. Se calcula en F_{p^6} = F_p[z]/(z^6 - g), donde
+/// z^6 = g y, si b'/b = g^k s^6, delta = z^k s.
+///
+/// Seguridad ante twists (SafeCurves): un ataque de punto invalido que solo
+/// usa la coordenada x cae en el twist cuadratico, y con formulas que no
+/// dependen de b en cualquiera de los seis; el mayor primo de cada orden
+/// mide el coste del logaritmo discreto alli.
+
+use num_integer::Integer;
+
+use super::curve::*;
+use super::ext::Fpk;
+use super::field::*;
+use super::fp::Field;
+use super::primes::*;
+use super::roots::*;
+
+/// Un twist sextico: clase g^class, representante b y #E(F_p).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SexticTwist {
+    pub class: usize,
+    pub b: u64,
+    pub order: u64,
+    pub largest_prime: u64,
+}
+
+/// Menor g que no es cuadrado ni cubo: su clase genera F_p* / (F_p*)^6.
+/// None si p ≢ 1 (mod 3).
+pub fn sextic_class_generator(p: u64) -> Option<u64> {
+    if p % 3 != 1 {
+        return None;
+    }
+    (2..p).find(|&g| legendre(g, p) == p - 1 && cubic_character(g, p) != 1)
+}
+
+/// Descompone c = g^k s^6 con 0 <= k < 6. None si c ≡ 0.
+pub fn sextic_class(c: u64, g: u64, p: u64) -> Option<(usize, u64)> {
+    let c = c % p;
+    if c == 0 {
+        return None;
+    }
+    // c^((p-1)/6) = (g^((p-1)/6))^k identifica k
+    let e = (p - 1) / 6;
+    let (zeta, omega) = (mod_pow(c, e, p), mod_pow(g, e, p));
+    let k = (0..6u64).find(|&k| mod_pow(omega, k, p) == zeta)?;
+    let sixth = mod_mul(c, mod_inv(mod_pow(g, k, p), p), p);
+    // raiz sexta = raiz cuadrada de la raiz cubica que sea cuadrado
+    let s = mod_cbrt_all(sixth, p)
+        .into_iter()
+        .find_map(|r| mod_sqrt(r, p))?;
+    Some((k as usize, s))
+}
+
+/// Los seis twists sexticos de y^2 = x^3 + b, en el orden b g^0, ..., b g^5.
+/// None si p ≢ 1 (mod 3) o b ≡ 0 (mod p).
+pub fn sextic_twists(b: u64, p: u64) -> Option<[SexticTwist; 6]> {
+    let g = sextic_class_generator(p)?;
+    if b.is_multiple_of(p) {
+        return None;
+    }
+    let mut twists = [SexticTwist { class: 0, b: 0, order: 0, largest_prime: 0 }; 6];
+    let mut bi = b % p;
+    for (class, twist) in twists.iter_mut().enumerate() {
+        let order = count_points_cm(bi, p)?;
+        let largest_prime = prime_factors(order).into_iter().max().unwrap_or(1);
+        *twist = SexticTwist { class, b: bi, order, largest_prime };
+        bi = mod_mul(bi, g, p);
+    }
+    Some(twists)
+}
+
+/// Isomorfismo E_from -> E_to entre dos twists sexticos, sobre F_{p^6}.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TwistMap {
+    pub from: u64,
+    pub to: u64,
+    /// Grado de F_p(delta): el menor cuerpo sobre el que el mapa esta definido.
+    pub degree: u32,
+    delta: Fpk<6>,
+}
+
+impl TwistMap {
+    /// Mapa (x, y) -> (delta^2 x, delta^3 y) con delta^6 = to / from.
+    /// None si p ≢ 1 (mod 3) o alguno de los b es 0.
+    pub fn new(from: u64, to: u64, p: u64) -> Option<Self> {
+        let g = sextic_class_generator(p)?;
+        let ratio = mod_mul(to, try_mod_inv(from % p, p).ok()?, p);
+        let (k, s) = sextic_class(ratio, g, p)?;
+        let field = sextic_field(g, p);
+        let delta = field.generator().pow(k as u64) * field.lift(s);
+        let degree = 6 / (k as u32).gcd(&6);
+        Some(TwistMap { from: from % p, to: to % p, degree, delta })
+    }
+
+    pub fn delta(&self) -> Fpk<6> {
+        self.delta
+    }
+
+    /// Mapa inverso E_to -> E_from (delta^-1).
+    pub fn inverse(&self) -> TwistMap {
+        TwistMap {
+            from: self.to,
+            to: self.from,
+            degree: self.degree,
+            delta: self.delta.inv().expect("delta != 0"),
+        }
+    }
+
+    /// Punto de E_from(F_p) visto en F_{p^6}.
+    pub fn embed(&self, pt: &Point) -> Option<(Fpk<6>, Fpk<6>)> {
+        pt.to_field(&self.delta)
+    }
+
+    /// Imagen de un punto de E_from(F_{p^6}) en E_to(F_{p^6}).
+    pub fn apply(&self, pt: Option<(Fpk<6>, Fpk<6>)>) -> Option<(Fpk<6>, Fpk<6>)> {
+        let d2 = self.delta.square();
+        let d3 = d2 * self.delta;
+        pt.map(|(x, y)| (d2 * x, d3 * y))
+    }
+}
+
+/// F_{p^6} = F_p[z]/(z^6 - g), irreducible porque g no es cuadrado ni cubo.
+fn sextic_field(g: u64, p: u64) -> Fpk<6> {
+    Fpk::new([0; 6], [mod_sub(0, g, p), 0, 0, 0, 0, 0], p)
+}
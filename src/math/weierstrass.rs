@@ -0,0 +1,164 @@
+/// Curvas de Weierstrass cortas generales y^2 = x^3 + ax + b sobre F_p.
+///
+/// `Curve` agrupa (a, b, p) para comparar las curvas D = -3 (j = 0, seis
+/// automorfismos) con curvas "normales" cuyo unico automorfismo es ±1,
+/// usando la misma aritmetica, busqueda de generador y ataques. Las
+/// funciones libres de `math::curve` son el caso a = 0.
+
+use std::fmt;
+
+use crate::error::{Error, Result};
+use super::field::*;
+use super::fp::*;
+use super::curve::*;
+use super::primes::*;
+use super::roots::quadratic_non_residue;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Curve {
+    pub a: u64,
+    pub b: u64,
+    pub p: u64,
+}
+
+impl Curve {
+    /// y^2 = x^3 + ax + b sobre F_p (p > 3), con a y b reducidos mod p.
+    pub fn new(a: u64, b: u64, p: u64) -> Self {
+        Curve { a: a % p, b: b % p, p }
+    }
+
+    fn a_fp(&self) -> Fp {
+        Fp::new(self.a, self.p)
+    }
+
+    fn b_fp(&self) -> Fp {
+        Fp::new(self.b, self.p)
+    }
+
+    /// 4a^3 + 27b^2, que se anula sii la curva es singular.
+    fn disc_factor(&self) -> Fp {
+        let (a, b) = (self.a_fp(), self.b_fp());
+        a.pow(3) * 4 + b.square() * 27
+    }
+
+    /// Discriminante Delta = -16 (4a^3 + 27b^2) mod p.
+    pub fn discriminant(&self) -> u64 {
+        (-(self.disc_factor() * 16)).value()
+    }
+
+    pub fn is_singular(&self) -> bool {
+        self.disc_factor().is_zero()
+    }
+
+    /// j = 1728 * 4a^3 / (4a^3 + 27b^2) mod p. None si la curva es singular.
+    pub fn j_invariant(&self) -> Option<u64> {
+        let den = self.disc_factor().inv()?;
+        Some((self.a_fp().pow(3) * (1728 * 4) * den).value())
+    }
+
+    /// |Aut(E)| sobre F_p: 6 si j = 0 y p ≡ 1 (mod 3), 4 si j = 1728 y
+    /// p ≡ 1 (mod 4), y 2 (solo ±1) en cualquier otro caso.
+    pub fn automorphism_count(&self) -> u64 {
+        if self.a == 0 && self.p % 3 == 1 {
+            6
+        } else if self.b == 0 && self.p % 4 == 1 {
+            4
+        } else {
+            2
+        }
+    }
+
+    /// Twist cuadratico y^2 = x^3 + a d^2 x + b d^3 con d no residuo: es
+    /// isomorfo a E sobre F_{p^2} pero no sobre F_p, y #E + #E' = 2p + 2.
+    pub fn quadratic_twist(&self) -> Curve {
+        let d = quadratic_non_residue(self.p).expect("p impar tiene no residuos");
+        let d2 = mod_mul(d, d, self.p);
+        Curve::new(mod_mul(self.a, d2, self.p), mod_mul(self.b, mod_mul(d2, d, self.p), self.p), self.p)
+    }
+
+    /// x^3 + ax + b.
+    fn rhs(&self, x: Fp) -> Fp {
+        x.pow(3) + self.a_fp() * x + self.b_fp()
+    }
+
+    pub fn is_on_curve(&self, pt: &Point) -> bool {
+        match pt.to_fp(self.p) {
+            None => true,
+            Some((x, y)) => y.square() == self.rhs(x),
+        }
+    }
+
+    pub fn point_add(&self, p1: &Point, p2: &Point) -> Point {
+        point_add_a(p1, p2, self.a, self.p)
+    }
+
+    pub fn point_double(&self, pt: &Point) -> Point {
+        point_double_a(pt, self.a, self.p)
+    }
+
+    pub fn point_neg(&self, pt: &Point) -> Point {
+        point_neg(pt, self.p)
+    }
+
+    pub fn scalar_mul(&self, k: u64, pt: &Point) -> Point {
+        scalar_mul_a(k, pt, self.a, self.p)
+    }
+
+    /// Suma P + Q comprobando antes que ambos esten en la curva.
+    pub fn try_point_add(&self, p1: &Point, p2: &Point) -> Result<Point> {
+        if !self.is_on_curve(p1) || !self.is_on_curve(p2) {
+            return Err(Error::PointNotOnCurve);
+        }
+        Ok(self.point_add(p1, p2))
+    }
+
+    /// #E(F_p) por fuerza bruta, incluyendo el punto en el infinito.
+    pub fn count_points(&self) -> u64 {
+        let mut count = 1u64; // punto en el infinito
+        for x in 0..self.p {
+            let rhs = self.rhs(Fp::new(x, self.p));
+            if rhs.is_zero() {
+                count += 1; // y = 0
+            } else if legendre(rhs.value(), self.p) == 1 {
+                count += 2; // dos raices
+            }
+        }
+        count
+    }
+
+    /// Punto de orden exactamente `order`: [order]P = O y [order/q]P != O
+    /// para cada primo q | order (factorizado con Pollard-rho/Brent).
+    pub fn find_generator(&self, order: u64) -> Point {
+        self.try_find_generator(order).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Como `find_generator`, con error `NoGenerator` si ningun punto tiene ese orden.
+    pub fn try_find_generator(&self, order: u64) -> Result<Point> {
+        if order == 0 {
+            return Err(Error::NoGenerator { order });
+        }
+        let factors = prime_factors(order);
+        for x in 1..self.p {
+            let x = Fp::new(x, self.p);
+            if let Some(y) = self.rhs(x).sqrt() {
+                if y.is_zero() {
+                    continue;
+                }
+                let pt = Point::from_fp(Some((x, y)));
+                if !self.scalar_mul(order, &pt).infinity {
+                    continue;
+                }
+                if factors.iter().all(|&q| !self.scalar_mul(order / q, &pt).infinity) {
+                    return Ok(pt);
+                }
+            }
+        }
+        Err(Error::NoGenerator { order })
+    }
+}
+
+impl fmt::Display for Curve {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "y^2 = x^3 + {}x + {} sobre F_{}", self.a, self.b, self.p)
+    }
+}
@@ -0,0 +1,218 @@
+/// Multiplicacion escalar con NAF de ventana w y comb de base fija.
+///
+/// wNAF recodifica k con digitos impares |d| < 2^(w-1) separados por al
+/// menos w - 1 ceros: con la tabla P, 3P, ..., (2^(w-1) - 1)P se hace una
+/// suma cada w + 1 bits en promedio, frente a una cada 2 bits del
+/// double-and-add binario. El comb de Lim-Lee precalcula 2^t - 1 sumas de
+/// los puntos 2^(i*d) P; para un punto fijo como G la tabla se amortiza y
+/// k*G cuesta d = ceil(bits/t) doblados y como mucho d sumas.
+/// Todo opera en coordenadas jacobianas con las tablas normalizadas a afin.
+
+use super::fp::Field;
+use super::jacobian::{batch_normalize, Jacobian};
+
+/// Ventana wNAF segun el tamano del escalar. La tabla de 2^(w-2) puntos
+/// cuesta una inversion, que hasta 64 bits cuesta mas de lo que ahorra:
+/// ahi se usa el NAF simple (w = 2, tabla = P, sin precalculo).
+pub fn wnaf_window(bits: u32) -> u32 {
+    match bits {
+        0..=64 => 2,
+        65..=160 => 4,
+        _ => 5,
+    }
+}
+
+/// `count` bits de k a partir de la posicion `pos` (limbs little-endian).
+fn bits_at(k: &[u64], pos: usize, count: usize) -> u64 {
+    let (limb, shift) = (pos / 64, pos % 64);
+    let mut v = k.get(limb).map_or(0, |&l| l >> shift);
+    if shift + count > 64 {
+        v |= k.get(limb + 1).map_or(0, |&l| l << (64 - shift));
+    }
+    v & ((1 << count) - 1)
+}
+
+/// Recodificacion wNAF de k (limbs u64 little-endian), digito menos
+/// significativo primero. Cada digito no nulo es impar y |d| < 2^(w-1).
+/// Recorre k con una ventana deslizante y un acarreo, sin copiarlo.
+pub fn wnaf(k: &[u64], w: u32) -> Vec<i8> {
+    assert!((2..=8).contains(&w), "ventana wNAF fuera de rango: {}", w);
+    let w = w as usize;
+    let len = k.iter().rposition(|&l| l != 0).map_or(0, |i| i * 64 + 64 - k[i].leading_zeros() as usize);
+    let mut digits = vec![0i8; len + 1];
+    let (mut carry, mut bit) = (0u64, 0usize);
+    while bit < len {
+        if bits_at(k, bit, 1) == carry {
+            bit += 1;
+            continue;
+        }
+        let now = w.min(len - bit);
+        // digito impar: ventana + acarreo, centrado en (-2^(w-1), 2^(w-1))
+        let mut word = (bits_at(k, bit, now) + carry) as i64;
+        carry = ((word >> (w - 1)) & 1) as u64;
+        word -= (carry as i64) << w;
+        digits[bit] = word as i8;
+        bit += now;
+    }
+    digits[len] = carry as i8;
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+    digits
+}
+
+/// P, 3P, 5P, ..., (2*count - 1)P en afin. 2P y las sumas quedan en
+/// jacobianas para pagar una sola inversion en todo el lote (ninguna si
+/// la tabla es solo P, como en el NAF con w = 2).
+fn odd_multiples<F: Field>(pt: (F, F), count: usize, a: F) -> Vec<Option<(F, F)>> {
+    if count == 1 {
+        return vec![Some(pt)];
+    }
+    batch_normalize(&odd_multiples_jacobian(pt, count, a))
+}
+
+/// Como `odd_multiples` sin normalizar, para juntar varias tablas en un
+/// mismo `batch_normalize` (MSM).
+pub(crate) fn odd_multiples_jacobian<F: Field>(pt: (F, F), count: usize, a: F) -> Vec<Jacobian<F>> {
+    let mut acc = Jacobian::from_affine(Some(pt), &pt.0);
+    let two_p = acc.double_a(a);
+    let mut table = Vec::with_capacity(count);
+    for _ in 0..count {
+        table.push(acc);
+        acc = acc.add_a(&two_p, a);
+    }
+    table
+}
+
+/// k*P por wNAF de ventana w en y^2 = x^3 + b.
+pub fn wnaf_mul<F: Field>(k: &[u64], pt: Option<(F, F)>, w: u32) -> Option<(F, F)> {
+    let pt = pt?;
+    wnaf_mul_with(k, pt, w, pt.0.zero(), Jacobian::double, Jacobian::add_affine)
+}
+
+/// Como `wnaf_mul` en y^2 = x^3 + ax + b.
+pub fn wnaf_mul_a<F: Field>(k: &[u64], pt: Option<(F, F)>, w: u32, a: F) -> Option<(F, F)> {
+    wnaf_mul_with(k, pt?, w, a, |acc| acc.double_a(a), |acc, q| acc.add_affine_a(q, a))
+}
+
+fn wnaf_mul_with<F: Field>(
+    k: &[u64],
+    pt: (F, F),
+    w: u32,
+    a: F,
+    double: impl Fn(&Jacobian<F>) -> Jacobian<F>,
+    add: impl Fn(&Jacobian<F>, Option<(F, F)>) -> Jacobian<F>,
+) -> Option<(F, F)> {
+    let digits = wnaf(k, w);
+    let table = odd_multiples(pt, 1 << (w - 2), a);
+    let mut acc = Jacobian::identity(&pt.0);
+    for &d in digits.iter().rev() {
+        acc = double(&acc);
+        if d > 0 {
+            acc = add(&acc, table[(d / 2) as usize]);
+        } else if d < 0 {
+            let neg = table[(-d / 2) as usize].map(|(x, y)| (x, -y));
+            acc = add(&acc, neg);
+        }
+    }
+    acc.to_affine()
+}
+
+/// Tabla del comb de Lim-Lee para un punto fijo P y escalares de hasta
+/// `bits` bits: `t` dientes separados `d = ceil(bits/t)` posiciones.
+#[derive(Clone, Debug)]
+pub struct CombTable<F: Field> {
+    /// table[j] = sum_{i : bit i de j} 2^(i*d) P, con table[0] = O.
+    table: Vec<Option<(F, F)>>,
+    teeth: usize,
+    spacing: usize,
+    bits: usize,
+    a: F,
+}
+
+impl<F: Field> CombTable<F> {
+    /// Tabla para y^2 = x^3 + b.
+    pub fn new(pt: (F, F), bits: usize, teeth: usize) -> Self {
+        Self::new_a(pt, bits, teeth, pt.0.zero())
+    }
+
+    /// Tabla para y^2 = x^3 + ax + b.
+    pub fn new_a(pt: (F, F), bits: usize, teeth: usize, a: F) -> Self {
+        assert!((1..=16).contains(&teeth), "numero de dientes fuera de rango: {}", teeth);
+        let spacing = bits.div_ceil(teeth).max(1);
+        let mut comb = CombTable { table: Vec::new(), teeth, spacing, bits, a };
+
+        // base[i] = 2^(i*d) P
+        let mut acc = Jacobian::from_affine(Some(pt), &pt.0);
+        let mut base = Vec::with_capacity(teeth);
+        for _ in 0..teeth {
+            base.push(acc);
+            for _ in 0..spacing {
+                acc = comb.double(&acc);
+            }
+        }
+        let base = batch_normalize(&base);
+
+        // table[j] = table[j sin su bit mas bajo] + base[bit mas bajo]
+        let mut table = vec![Jacobian::identity(&pt.0); 1 << teeth];
+        for j in 1..table.len() {
+            let low = j.trailing_zeros() as usize;
+            table[j] = comb.add(&table[j & (j - 1)], base[low]);
+        }
+        comb.table = batch_normalize(&table);
+        comb
+    }
+
+    fn double(&self, pt: &Jacobian<F>) -> Jacobian<F> {
+        if self.a.is_zero() { pt.double() } else { pt.double_a(self.a) }
+    }
+
+    fn add(&self, pt: &Jacobian<F>, q: Option<(F, F)>) -> Jacobian<F> {
+        if self.a.is_zero() { pt.add_affine(q) } else { pt.add_affine_a(q, self.a) }
+    }
+
+    /// k*P con d doblados y como mucho d sumas mixtas. Requiere k < 2^bits.
+    pub fn mul(&self, k: &[u64]) -> Option<(F, F)> {
+        let bit = |i: usize| k.get(i / 64).map_or(0, |&limb| ((limb >> (i % 64)) & 1) as usize);
+        assert!(
+            (self.bits..k.len() * 64).all(|i| bit(i) == 0),
+            "el escalar excede los {} bits de la tabla comb",
+            self.bits
+        );
+        let mut acc = Jacobian::identity(&self.a);
+        for col in (0..self.spacing).rev() {
+            acc = self.double(&acc);
+            let idx = (0..self.teeth).fold(0, |idx, i| idx | (bit(i * self.spacing + col) << i));
+            if idx != 0 {
+                acc = self.add(&acc, self.table[idx]);
+            }
+        }
+        acc.to_affine()
+    }
+}
+
+/// Coste de una multiplicacion escalar en operaciones de grupo.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MulCost {
+    pub doubles: usize,
+    pub adds: usize,
+}
+
+impl MulCost {
+    pub fn total(&self) -> usize {
+        self.doubles + self.adds
+    }
+}
+
+/// Operaciones que hace `wnaf_mul(k, P, w)`: la tabla de multiplos impares
+/// (2P y 2^(w-2) - 1 sumas) y, en el bucle, un doblado por digito salvo el
+/// primero y una suma por digito no nulo salvo el primero.
+pub fn wnaf_cost(k: &[u64], w: u32) -> MulCost {
+    let digits = wnaf(k, w);
+    let nonzero = digits.iter().filter(|&&d| d != 0).count();
+    let table = (1usize << (w - 2)) - 1;
+    MulCost {
+        doubles: digits.len().saturating_sub(1) + usize::from(table > 0),
+        adds: nonzero.saturating_sub(1) + table,
+    }
+}
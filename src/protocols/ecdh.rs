@@ -0,0 +1,24 @@
+/// ECDH sobre secp256k1 con la escalera de tiempo constante.
+///
+/// El secreto compartido es la coordenada x de d_A * Q_B = d_B * Q_A.
+
+use crate::math::fp::Field;
+use crate::math::secp256k1::ct::*;
+use crate::math::secp256k1::*;
+
+/// Clave publica Q = d*G. None si d == 0.
+pub fn public_key(secret: &Scalar) -> Option<Affine> {
+    if secret.is_zero() {
+        return None;
+    }
+    Some(mul_generator_ct(secret))
+}
+
+/// Secreto compartido x(d * Q). None si Q no esta en la curva o el
+/// resultado es el infinito (clave publica invalida o d == 0).
+pub fn shared_secret(secret: &Scalar, peer: &Affine) -> Option<FieldElement> {
+    if peer.is_none() || !is_on_curve(peer) {
+        return None;
+    }
+    scalar_mul_ct(secret, peer).map(|(x, _)| x)
+}
@@ -0,0 +1,56 @@
+/// ECDSA sobre secp256k1.
+///
+/// La firma usa solo operaciones de tiempo constante sobre d y k (escalera
+/// de Montgomery e inverso de Fermat). La verificacion trabaja con datos
+/// publicos y usa la ley de grupo afin normal. El nonce lo elige el
+/// llamador: reutilizarlo entre dos mensajes revela la clave privada.
+
+use crate::math::fp::Field;
+use crate::math::secp256k1::ct::*;
+use crate::math::secp256k1::*;
+use crate::endomorphism::secp256k1::msm;
+
+/// Firma (r, s) con r, s en [1, n).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Signature {
+    pub r: Scalar,
+    pub s: Scalar,
+}
+
+/// x(R) reducido modulo n.
+fn x_mod_n(pt: &Affine) -> Option<Scalar> {
+    pt.map(|(x, _)| Scalar::from_be_bytes_reduced(&x.to_be_bytes()))
+}
+
+/// Firma el hash `z` (ya reducido modulo n) con la clave `secret` y el
+/// nonce `nonce`. None si el nonce produce r == 0 o s == 0.
+pub fn sign(secret: &Scalar, z: &Scalar, nonce: &Scalar) -> Option<Signature> {
+    let r = x_mod_n(&mul_generator_ct(nonce))?;
+    if r.is_zero() {
+        return None;
+    }
+    // s = k^-1 * (z + r*d)
+    let s = nonce.invert_ct() * (*z + r * *secret);
+    if s.is_zero() {
+        return None;
+    }
+    Some(Signature { r, s })
+}
+
+/// Verifica la firma de `z` bajo la clave publica `public`.
+pub fn verify(public: &Affine, z: &Scalar, sig: &Signature) -> bool {
+    if public.is_none() || !is_on_curve(public) {
+        return false;
+    }
+    if sig.r.is_zero() || sig.s.is_zero() {
+        return false;
+    }
+    let w = match sig.s.inv() {
+        Some(w) => w,
+        None => return false,
+    };
+    let u1 = *z * w;
+    let u2 = sig.r * w;
+    let pt = msm(&[u1, u2], &[GENERATOR, *public]);
+    x_mod_n(&pt) == Some(sig.r)
+}
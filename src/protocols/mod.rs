@@ -0,0 +1,12 @@
+/// Protocolos de demostración sobre secp256k1: módulo contenedor.
+///
+/// - `ecdh`: acuerdo de claves Diffie-Hellman
+/// - `ecdsa`: firma y verificación ECDSA (nonce provisto por el llamador)
+///
+/// Toda operación con un escalar secreto pasa por `math::secp256k1::ct`.
+
+pub mod ecdh;
+pub mod ecdsa;
+
+pub use ecdh::*;
+pub use ecdsa::*;